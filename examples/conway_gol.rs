@@ -8,24 +8,22 @@ use std::{
 const N: usize = 17;
 const STEPS: usize = 100;
 
-fn update(mat: &mut Ndarr<i8, U2>) {
+fn update(mat: &mut Ndarr<bool, U2>) {
     let rolls = Ndarr::from([1, 0, -1]);
+    let cells = mat.map(|x| *x as u8);
     let out = rolls
-        .map(|r| mat.roll(*r, 0))
+        .map(|r| cells.roll(*r, 0))
         .outer_product(&rolls, |a, r| a.roll(r, 1))
         .sum();
-    mat.bimap_in_place(&out, |prev, new| {
-        if new == 3 || (prev == 1 && (new == 4)) {
-            1
-        } else {
-            0
-        }
-    })
+    //the sum includes the cell itself, so a live cell with 3 neighbours has a count of 4
+    let born = out.map(|n| *n == 3);
+    let survives = out.map(|n| *n == 4);
+    *mat = born | (&*mat & survives);
 }
 
 fn main() {
-    //initialize game matrix with random 0 or 1
-    let mut x = NdarrRand::choose(&[0, 1], [N, N], None);
+    //initialize game matrix with random dead or alive cells
+    let mut x = NdarrRand::choose(&[false, true], [N, N], None);
     let mut stdout = stdout();
     stdout.flush().unwrap();
     stdout.write_all(b"\x1B[2J\x1B[1;1H").unwrap();
@@ -33,17 +31,18 @@ fn main() {
     for i in 0..STEPS {
         update(&mut x); //call update function
         let vis = x.map(|x| {
-            if *x == 0 {
-                "░".to_string()
-            } else {
+            if *x {
                 "█".to_string()
+            } else {
+                "░".to_string()
             }
         }); //make it pretty
         println!("{}", vis);
         println!(
-            "\n Conway's Game of Life using rapl: [Step {} out of {}] \n",
+            "\n Conway's Game of Life using rapl: [Step {} out of {}, {} alive cells] \n",
             i + 1,
-            STEPS
+            STEPS,
+            x.count_nonzero()
         );
         sleep(Duration::from_millis(50));
         stdout.write_all(b"\x1B[1;1H").unwrap();
@@ -1,4 +1,4 @@
-use num_traits::{Num, Signed, Unsigned};
+use num_traits::{Num, One, Signed, Unsigned, Zero};
 use std::{
     fmt::{Debug, Display},
     ops::{Add, AddAssign, Div, Mul, MulAssign, Neg},
@@ -31,6 +31,27 @@ impl<T: Copy + PartialEq> C<T> {
     }
 }
 
+impl<T: Copy + PartialEq + Zero> Zero for C<T>
+where
+    C<T>: Add<Output = C<T>>,
+{
+    fn zero() -> Self {
+        C(T::zero(), T::zero())
+    }
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero()
+    }
+}
+
+impl<T: Copy + PartialEq + Zero + One> One for C<T>
+where
+    C<T>: Mul<Output = C<T>>,
+{
+    fn one() -> Self {
+        C(T::one(), T::zero())
+    }
+}
+
 impl<T: Copy + PartialEq + Neg<Output = T>> C<T> {
     pub fn conj(&self) -> C<T> {
         C(self.0, -self.1)
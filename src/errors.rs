@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(Debug)]
 pub struct DimError {
     details: String,
@@ -10,3 +12,59 @@ impl DimError {
         }
     }
 }
+
+impl fmt::Display for DimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.details)
+    }
+}
+
+impl std::error::Error for DimError {}
+
+#[derive(Debug)]
+pub struct CastError {
+    details: String,
+}
+
+impl CastError {
+    pub fn new(msg: &str) -> CastError {
+        CastError {
+            details: msg.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.details)
+    }
+}
+
+impl std::error::Error for CastError {}
+
+#[derive(Debug)]
+pub struct LinalgError {
+    details: String,
+}
+
+impl LinalgError {
+    pub fn new(msg: &str) -> LinalgError {
+        LinalgError {
+            details: msg.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.details)
+    }
+}
+
+impl std::error::Error for LinalgError {}
+
+impl From<DimError> for LinalgError {
+    fn from(e: DimError) -> Self {
+        LinalgError { details: e.details }
+    }
+}
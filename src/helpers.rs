@@ -10,3 +10,15 @@ where
     }
     result
 }
+
+//Total order for `PartialOrd` types, the unordered values (e.g. NaN) compare greater than everything else and equal to each other,
+//so they are sorted last. A `partial_cmp(..).unwrap_or(Equal)` comparator is not transitive once NaN is present and makes `sort_by` panic.
+pub(crate) fn nan_last_cmp<T: PartialOrd>(a: &T, b: &T) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    match (a.partial_cmp(a).is_none(), b.partial_cmp(b).is_none()) {
+        (false, false) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+    }
+}
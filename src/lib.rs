@@ -20,10 +20,13 @@ mod display;
 mod errors;
 mod helpers;
 mod indexing;
+pub mod linalg;
 mod natives;
 pub mod ops;
+mod promote;
 mod scalars;
 mod shape;
+pub mod sparse;
 
 pub mod utils;
 
@@ -33,8 +36,9 @@ use std::{fmt::Debug, fmt::Display};
 #[cfg(feature = "complex")]
 pub mod complex;
 
-pub use errors::DimError;
+pub use errors::{CastError, DimError, LinalgError};
 use num_traits::Float;
+pub use promote::{Promote, PromoteScalar, Promoted, PromotedScalar};
 pub use scalars::Scalar;
 
 #[cfg(feature = "complex")]
@@ -113,6 +117,29 @@ impl<T: Clone, R: Unsigned> Ndarr<T, R> {
         })
     }
 
+    /// Converts the array into an array of dynamic rank, where the rank is only known at runtime.
+    pub fn into_notyped(self) -> Ndarr<T, UTerm> {
+        Ndarr {
+            data: self.data,
+            dim: Dim::<UTerm>::new(&self.dim.shape).unwrap(),
+        }
+    }
+
+    /// Converts the array into an array of typed rank `R2`. Returns Error if the rank of the array is not `R2`.
+    pub fn into_rank<R2: Unsigned>(self) -> Result<Ndarr<T, R2>, DimError> {
+        if self.rank() != R2::to_usize() {
+            return Err(DimError::new(&format!(
+                "Can not convert array with shape {:?} to rank {}.",
+                self.dim.shape,
+                R2::to_usize()
+            )));
+        }
+        Ok(Ndarr {
+            data: self.data,
+            dim: Dim::new(&self.dim.shape)?,
+        })
+    }
+
     //Slice an Ndarr of rank `R` into rust vector of Ndarrs of rank `R-1` by splitting the original array along the specified axis
     pub fn slice_at(&self, axis: usize) -> Vec<Ndarr<T, Sub1<R>>>
     where
@@ -565,6 +592,9 @@ mod tests {
         assert_eq!(cumsum_l1, Ndarr::from([[3, 2], [7, 4]]));
         //let arr2 = Ndarr::from([0.1, 0.2, 0.3]);
         //let sump = arr2.scanr(0, |x, y| x + y);
+        let empty = Ndarr::<i32, U2>::new(&[], [0, 2]).unwrap();
+        assert_eq!(empty.scanr(0, |x, y| x + y), empty);
+        assert_eq!(empty.scanl(1, |x, y| x + y), empty);
     }
 
     #[test]
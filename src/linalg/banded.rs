@@ -0,0 +1,338 @@
+use super::batched::{map_stack, solve_stack};
+use super::*;
+
+///Solves `A x = b` for a stack of triangular matrices `A`, only the `lower` or upper triangle of `A` is read.
+///With `unit_diag` the diagonal is assumed to be all ones and is not read. The right-hand side follows the rules of `solve`.
+///Returns Error if a diagonal element is zero.
+///```
+///use rapl::*;
+///let l = Ndarr::from([[2., 0.], [1., 4.]]);
+///let x = linalg::solve_triangular(&l, &Ndarr::from([4., 10.]), true, false).unwrap();
+///assert_eq!(x, Ndarr::from([2., 2.]));
+///```
+pub fn solve_triangular<T: LinalgScalar, R1: Unsigned, R2: Unsigned>(
+    a: &Ndarr<T, R1>,
+    b: &Ndarr<T, R2>,
+    lower: bool,
+    unit_diag: bool,
+) -> Result<Ndarr<T, R2>, LinalgError> {
+    let (a_stack, matrices) = map_stack(a, |m| {
+        let n = check_square(m)?;
+        let mut m = Mat::from_ndarr(m);
+        for i in 0..n {
+            if unit_diag {
+                m.set(i, i, T::one());
+            } else if m.get(i, i) == T::zero() {
+                return Err(LinalgError::new(
+                    "Singular matrix, the triangular system has no unique solution.",
+                ));
+            }
+        }
+        Ok(m)
+    })?;
+    let n = a.shape()[a.rank() - 1];
+    solve_stack(&a_stack, a.rank(), n, b, |p, rhs| {
+        Ok(if lower {
+            forward_substitute(&matrices[p], rhs)
+        } else {
+            back_substitute(&matrices[p], rhs, n)
+        })
+    })
+}
+
+///Solves `A x = b` for a tridiagonal matrix `A` with the Thomas algorithm, `sub`, `diag` and `sup` are the diagonals
+///below, on and above the main diagonal. The right-hand side follows the rules of `solve` for a single matrix.
+///There is no pivoting, so `A` should be diagonally dominant or positive definite. Returns Error if a pivot vanishes.
+///```
+///use rapl::*;
+///let (sub, sup) = (Ndarr::from([-1., -1.]), Ndarr::from([-1., -1.]));
+///let diag = Ndarr::from([2., 2., 2.]);
+///let x = linalg::solve_tridiagonal(&sub, &diag, &sup, &Ndarr::from([1., 0., 1.])).unwrap();
+///assert!(x.data.iter().all(|x: &f64| (x - 1.).abs() < 1e-12));
+///```
+pub fn solve_tridiagonal<T: LinalgScalar, R: Unsigned>(
+    sub: &Ndarr<T, U1>,
+    diag: &Ndarr<T, U1>,
+    sup: &Ndarr<T, U1>,
+    rhs: &Ndarr<T, R>,
+) -> Result<Ndarr<T, R>, LinalgError> {
+    let n = diag.len();
+    if sub.len() + 1 != n.max(1) || sup.len() + 1 != n.max(1) {
+        return Err(LinalgError::new(&format!(
+            "Expected off diagonals of length {}, found {} and {}.",
+            n.saturating_sub(1),
+            sub.len(),
+            sup.len()
+        )));
+    }
+    let pivot_error = || LinalgError::new("Zero pivot found in the tridiagonal solve.");
+    solve_stack(&[], 2, n, rhs, |_, b| {
+        let mut x = b.clone();
+        let mut c = vec![T::zero(); n];
+        for j in 0..b.cols {
+            //forward sweep, `c` holds the modified super diagonal
+            for i in 0..n {
+                let pivot = if i > 0 {
+                    x.set(i, j, x.get(i, j) - sub.data[i - 1] * x.get(i - 1, j));
+                    diag.data[i] - sub.data[i - 1] * c[i - 1]
+                } else {
+                    diag.data[0]
+                };
+                if pivot == T::zero() {
+                    return Err(pivot_error());
+                }
+                if i + 1 < n {
+                    c[i] = sup.data[i] / pivot;
+                }
+                x.set(i, j, x.get(i, j) / pivot);
+            }
+            for i in (0..n.saturating_sub(1)).rev() {
+                x.set(i, j, x.get(i, j) - c[i] * x.get(i + 1, j));
+            }
+        }
+        Ok(x)
+    })
+}
+
+///Band matrix in the compact storage of LAPACK: an `n x n` matrix with `lower` diagonals below and `upper` above the main
+///diagonal is stored in a `(lower + upper + 1) x n` array whose element `[upper + i - j, j]` is the element `[i, j]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandMatrix<T: Clone> {
+    lower: usize,
+    upper: usize,
+    bands: Ndarr<T, U2>,
+}
+
+impl<T: LinalgScalar> BandMatrix<T> {
+    ///Band matrix from its compact storage. Returns Error if `bands` does not have `lower + upper + 1` rows.
+    ///```
+    ///use rapl::*;
+    ///use rapl::linalg::BandMatrix;
+    ///let bands = Ndarr::from([[0., 1., 1.], [4., 4., 4.]]);
+    ///let a = BandMatrix::new(0, 1, bands).unwrap();
+    ///assert_eq!(a.to_dense(), Ndarr::from([[4., 1., 0.], [0., 4., 1.], [0., 0., 4.]]));
+    ///```
+    pub fn new(lower: usize, upper: usize, bands: Ndarr<T, U2>) -> Result<Self, LinalgError> {
+        if bands.shape()[0] != lower + upper + 1 {
+            return Err(LinalgError::new(&format!(
+                "Expected {} bands, found shape {:?}.",
+                lower + upper + 1,
+                bands.shape()
+            )));
+        }
+        Ok(BandMatrix {
+            lower,
+            upper,
+            bands,
+        })
+    }
+
+    ///Band matrix holding the `lower` diagonals below and `upper` above the main diagonal of a square matrix,
+    ///the elements outside the band are ignored.
+    pub fn from_dense(a: &Ndarr<T, U2>, lower: usize, upper: usize) -> Result<Self, LinalgError> {
+        let n = check_square(a)?;
+        let rows = lower + upper + 1;
+        let mut data = vec![T::zero(); rows * n];
+        for j in 0..n {
+            for i in j.saturating_sub(upper)..n.min(j + lower + 1) {
+                data[(upper + i - j) * n + j] = a.data[i * n + j];
+            }
+        }
+        BandMatrix::new(
+            lower,
+            upper,
+            Ndarr {
+                data,
+                dim: Dim::new(&[rows, n])?,
+            },
+        )
+    }
+
+    ///Number of diagonals below and above the main diagonal.
+    pub fn bandwidth(&self) -> (usize, usize) {
+        (self.lower, self.upper)
+    }
+
+    ///Compact storage of the matrix.
+    pub fn bands(&self) -> &Ndarr<T, U2> {
+        &self.bands
+    }
+
+    ///Size of the square matrix.
+    pub fn size(&self) -> usize {
+        self.bands.shape()[1]
+    }
+
+    ///Dense `n x n` matrix.
+    pub fn to_dense(&self) -> Ndarr<T, U2> {
+        let n = self.size();
+        let mut a = Mat::zeros(n, n);
+        for j in 0..n {
+            for i in j.saturating_sub(self.upper)..n.min(j + self.lower + 1) {
+                a.set(i, j, self.bands.data[(self.upper + i - j) * n + j]);
+            }
+        }
+        a.into_ndarr()
+    }
+}
+
+///Solves `A x = b` for a band matrix `A` by Gaussian elimination with partial pivoting restricted to the band.
+///The right-hand side follows the rules of `solve` for a single matrix. Returns Error if `A` is singular.
+///```
+///use rapl::*;
+///use rapl::linalg::BandMatrix;
+///let a = BandMatrix::from_dense(&Ndarr::from([[1., 2., 0.], [3., 1., 1.], [0., 2., 1.]]), 1, 1).unwrap();
+///let x = linalg::solve_banded(&a, &Ndarr::from([3., 5., 3.])).unwrap();
+///assert!(x.data.iter().all(|x: &f64| (x - 1.).abs() < 1e-12));
+///```
+pub fn solve_banded<T: LinalgScalar, R: Unsigned>(
+    a: &BandMatrix<T>,
+    b: &Ndarr<T, R>,
+) -> Result<Ndarr<T, R>, LinalgError> {
+    let (n, l, u) = (a.size(), a.lower, a.upper);
+    //row `i` keeps the columns `i - l ..= i + l + u`, room for the fill in of the row swaps
+    let width = 2 * l + u + 1;
+    let pos = |i: usize, j: usize| i * width + j + l - i;
+    let mut lu = vec![T::zero(); n * width];
+    for j in 0..n {
+        for i in j.saturating_sub(u)..n.min(j + l + 1) {
+            lu[pos(i, j)] = a.bands.data[(u + i - j) * n + j];
+        }
+    }
+    let mut perm: Vec<usize> = Vec::with_capacity(n);
+    for k in 0..n {
+        let last = n.min(k + l + 1);
+        let p = (k..last)
+            .max_by(|x, y| {
+                lu[pos(*x, k)]
+                    .modulus()
+                    .partial_cmp(&lu[pos(*y, k)].modulus())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap();
+        //as in `solve`, only an exactly zero or non-finite pivot is singular
+        let pivot = lu[pos(p, k)].modulus();
+        if pivot == T::Real::zero() || !pivot.is_finite() {
+            return Err(LinalgError::new(
+                "Singular matrix, the system has no unique solution.",
+            ));
+        }
+        perm.push(p);
+        let end = n.min(k + l + u + 1);
+        if p != k {
+            for j in k..end {
+                lu.swap(pos(k, j), pos(p, j));
+            }
+        }
+        for i in k + 1..last {
+            let m = lu[pos(i, k)] / lu[pos(k, k)];
+            lu[pos(i, k)] = m;
+            for j in k + 1..end {
+                lu[pos(i, j)] = lu[pos(i, j)] - m * lu[pos(k, j)];
+            }
+        }
+    }
+    solve_stack(&[], 2, n, b, |_, rhs| {
+        let mut x = rhs.clone();
+        for k in 0..n {
+            x.swap_rows(k, perm[k]);
+            for i in k + 1..n.min(k + l + 1) {
+                for j in 0..x.cols {
+                    x.set(i, j, x.get(i, j) - lu[pos(i, k)] * x.get(k, j));
+                }
+            }
+        }
+        for j in 0..x.cols {
+            for i in (0..n).rev() {
+                let mut s = x.get(i, j);
+                for k in i + 1..n.min(i + l + u + 1) {
+                    s = s - lu[pos(i, k)] * x.get(k, j);
+                }
+                x.set(i, j, s / lu[pos(i, i)]);
+            }
+        }
+        Ok(x)
+    })
+}
+
+#[cfg(test)]
+mod banded_test {
+    use super::*;
+
+    #[test]
+    fn triangular() {
+        let a: Ndarr<f64, U2> = Ndarr::from([[2., 7., 7.], [1., 3., 7.], [4., -1., 5.]]);
+        let b = Ndarr::from([[1., 2.], [3., 4.], [5., 6.]]);
+        let lower = Ndarr::from([[2., 0., 0.], [1., 3., 0.], [4., -1., 5.]]);
+        let x = solve_triangular(&a, &b, true, false).unwrap();
+        assert!(close(&lower.mat_mul(&x), &b, 1e-12));
+        let upper = Ndarr::from([[2., 7., 7.], [0., 3., 7.], [0., 0., 5.]]);
+        let x = solve_triangular(&a, &b, false, false).unwrap();
+        assert!(close(&upper.mat_mul(&x), &b, 1e-12));
+        let unit = Ndarr::from([[1., 0., 0.], [1., 1., 0.], [4., -1., 1.]]);
+        let x = solve_triangular(&a, &b, true, true).unwrap();
+        assert!(close(&unit.mat_mul(&x), &b, 1e-12));
+        //stack of matrices with a stack of vectors
+        let stack: Ndarr<f64, U3> = Ndarr::from([[[1., 0.], [2., 1.]], [[2., 0.], [0., 4.]]]);
+        let x = solve_triangular(&stack, &Ndarr::from([[1., 4.], [2., 4.]]), true, false).unwrap();
+        assert_eq!(x, Ndarr::from([[1., 2.], [1., 1.]]));
+        let singular = Ndarr::from([[1., 0.], [1., 0.]]);
+        assert!(solve_triangular(&singular, &Ndarr::from([1., 1.]), true, false).is_err());
+        assert!(solve_triangular(&singular, &Ndarr::from([1., 1.]), true, true).is_ok());
+    }
+
+    #[test]
+    fn tridiagonal() {
+        let (sub, diag, sup) = (
+            Ndarr::from([1., 2., -1.]),
+            Ndarr::from([4., 5., 6., 3.]),
+            Ndarr::from([2., -1., 1.]),
+        );
+        let a = BandMatrix::new(
+            1,
+            1,
+            Ndarr::from([[0., 2., -1., 1.], [4., 5., 6., 3.], [1., 2., -1., 0.]]),
+        )
+        .unwrap()
+        .to_dense();
+        let b: Ndarr<f64, U3> = Ndarr::from([[[1., 0.], [2., 1.], [3., 0.], [4., 1.]]; 2]);
+        let x = solve_tridiagonal(&sub, &diag, &sup, &b).unwrap();
+        assert!(close(&x, &solve(&a, &b).unwrap(), 1e-12));
+        assert!(solve_tridiagonal(&sub, &diag, &Ndarr::from([1.]), &b).is_err());
+        let zero = Ndarr::from([0., 1.]);
+        assert!(solve_tridiagonal(&Ndarr::from([1.]), &zero, &Ndarr::from([1.]), &zero).is_err());
+        let empty: Ndarr<f64, U1> = Ndarr::from(Vec::new());
+        let x = solve_tridiagonal(&empty, &empty, &empty, &empty).unwrap();
+        assert_eq!(x.shape(), &[0]);
+    }
+
+    #[test]
+    fn banded() {
+        let a: Ndarr<f64, U2> = Ndarr::from([
+            [1., 3., 0., 0., 0.],
+            [4., 1., 2., 0., 0.],
+            [-2., 5., 1., 1., 0.],
+            [0., 1., 2., 0., 3.],
+            [0., 0., -1., 6., 2.],
+        ]);
+        let band = BandMatrix::from_dense(&a, 2, 1).unwrap();
+        assert_eq!(band.bandwidth(), (2, 1));
+        assert_eq!(band.bands().shape(), &[4, 5]);
+        assert_eq!(band.to_dense(), a);
+        let b = Ndarr::from([1., 2., 3., 4., 5.]);
+        let x = solve_banded(&band, &b).unwrap();
+        assert!(close(&x, &solve(&a, &b).unwrap(), 1e-12));
+        let bm = Ndarr::from([[1., 0.], [2., 1.], [0., 0.], [1., 1.], [-3., 2.]]);
+        let x = solve_banded(&band, &bm).unwrap();
+        assert!(close(&a.mat_mul(&x), &bm, 1e-12));
+        assert!(BandMatrix::new(1, 1, Ndarr::from([[1., 2.]])).is_err());
+        let singular = BandMatrix::from_dense(&Ndarr::from([[1., 1.], [1., 1.]]), 1, 1).unwrap();
+        assert!(solve_banded(&singular, &Ndarr::from([1., 2.])).is_err());
+        //NaN must not break the pivot search, it reaches the second pivot through the elimination
+        let nan = BandMatrix::from_dense(&Ndarr::from([[f64::NAN, 1.], [1., 1.]]), 1, 1).unwrap();
+        assert!(solve_banded(&nan, &Ndarr::from([1., 2.])).is_err());
+        let scaled = BandMatrix::from_dense(&Ndarr::from([[1e-20, 0.], [0., 1.]]), 1, 1).unwrap();
+        let x = solve_banded(&scaled, &Ndarr::from([1e-20, 1.])).unwrap();
+        assert_eq!(x, Ndarr::from([1., 1.]));
+    }
+}
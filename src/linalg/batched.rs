@@ -0,0 +1,346 @@
+//!Linear algebra on stacks of matrices: the last two axes of an array of rank at least 2 are the matrices and the
+//!leading axes are the stack. The functions work on single matrices `Ndarr<T, U2>` as well.
+use super::lu::{self, lu_nonsingular};
+use super::*;
+use crate::helpers::multiply_list;
+
+type Stack<T, R> = Ndarr<T, Sub1<Sub1<R>>>;
+type Vectors<T, R> = Ndarr<T, Sub1<R>>;
+type SlogdetResult<T, R> = Result<(Stack<T, R>, Stack<<T as LinalgScalar>::Real, R>), LinalgError>;
+type EighResult<T, R> = Result<(Vectors<<T as LinalgScalar>::Real, R>, Ndarr<T, R>), LinalgError>;
+#[cfg(feature = "complex")]
+type EigResult<T, R> = Result<(Vectors<C<T>, R>, Ndarr<C<T>, R>), LinalgError>;
+type Unstacked<T> = Result<(Vec<usize>, Vec<Ndarr<T, U2>>), LinalgError>;
+type SvdResult<T, R> = Result<
+    (
+        Ndarr<T, R>,
+        Vectors<<T as LinalgScalar>::Real, R>,
+        Ndarr<T, R>,
+    ),
+    LinalgError,
+>;
+
+//Shape of the stack and the matrices of an array of rank at least 2, in row-major order of the stack.
+fn unstack<T: LinalgScalar, R: Unsigned>(a: &Ndarr<T, R>) -> Unstacked<T> {
+    let shape = a.shape();
+    if shape.len() < 2 {
+        return Err(LinalgError::new(&format!(
+            "Expected an array of rank at least 2, found shape {:?}.",
+            shape
+        )));
+    }
+    let (stack, core) = shape.split_at(shape.len() - 2);
+    let size = core[0] * core[1];
+    let matrices = (0..multiply_list(stack, 1))
+        .map(|i| Ndarr {
+            data: a.data[i * size..(i + 1) * size].to_vec(),
+            dim: Dim::new(core).unwrap(),
+        })
+        .collect();
+    Ok((stack.to_vec(), matrices))
+}
+
+//Assembles the results for every matrix of a stack, all of shape `core`, into an array of shape `stack ++ core`.
+fn restack<T: Clone + Debug, R: Unsigned>(
+    stack: &[usize],
+    core: &[usize],
+    parts: Vec<Vec<T>>,
+) -> Result<Ndarr<T, R>, LinalgError> {
+    let shape: Vec<usize> = stack.iter().chain(core).copied().collect();
+    Ok(Ndarr {
+        data: parts.into_iter().flatten().collect(),
+        dim: Dim::new(&shape)?,
+    })
+}
+
+//Applies `f` to every matrix of the stack.
+pub(crate) fn map_stack<T: LinalgScalar, R: Unsigned, U, F>(
+    a: &Ndarr<T, R>,
+    f: F,
+) -> Result<(Vec<usize>, Vec<U>), LinalgError>
+where
+    F: Fn(&Ndarr<T, U2>) -> Result<U, LinalgError>,
+{
+    let (stack, matrices) = unstack(a)?;
+    let results = matrices.iter().map(f).collect::<Result<Vec<U>, _>>()?;
+    Ok((stack, results))
+}
+
+///Determinant of every matrix of a stack.
+///```
+///use rapl::*;
+///let a = Ndarr::from([[[2., 0.], [0., 3.]], [[1., 2.], [3., 4.]]]);
+///assert_eq!(linalg::det(&a).unwrap(), Ndarr::from([6., -2.]));
+///```
+pub fn det<T: LinalgScalar, R>(a: &Ndarr<T, R>) -> Result<Stack<T, R>, LinalgError>
+where
+    R: Unsigned + Sub<B1>,
+    Sub1<R>: Sub<B1>,
+    Sub1<Sub1<R>>: Unsigned,
+{
+    let (stack, d) = map_stack(a, |m| m.det())?;
+    restack(&stack, &[], d.into_iter().map(|x| vec![x]).collect())
+}
+
+///Sign and natural logarithm of the absolute value of the determinant of every matrix of a stack, see the `slogdet` method for single matrices.
+pub fn slogdet<T: LinalgScalar, R>(a: &Ndarr<T, R>) -> SlogdetResult<T, R>
+where
+    R: Unsigned + Sub<B1>,
+    Sub1<R>: Sub<B1>,
+    Sub1<Sub1<R>>: Unsigned,
+{
+    let (stack, d) = map_stack(a, |m| m.slogdet())?;
+    let (signs, logs): (Vec<Vec<T>>, Vec<Vec<T::Real>>) =
+        d.into_iter().map(|(s, l)| (vec![s], vec![l])).unzip();
+    Ok((restack(&stack, &[], signs)?, restack(&stack, &[], logs)?))
+}
+
+///Inverse of every matrix of a stack. Returns Error if any of them is singular.
+///`inv` on complex arrays is the element wise inverse, so the matrix inverse is a function of this module.
+///```
+///use rapl::*;
+///let a = Ndarr::from([[2., 0.], [0., 4.]]);
+///assert_eq!(linalg::inv(&a).unwrap(), Ndarr::from([[0.5, 0.], [0., 0.25]]));
+///```
+pub fn inv<T: LinalgScalar, R: Unsigned>(a: &Ndarr<T, R>) -> Result<Ndarr<T, R>, LinalgError> {
+    let (stack, parts) = map_stack(a, |m| Ok(lu::inv(m)?.data))?;
+    restack(&stack, &a.shape()[a.rank() - 2..], parts)
+}
+
+///Solves the linear systems `A x = b` for a stack of square matrices `A`. If the rank of `b` is one less than the rank of
+///`A`, `b` is a stack of vectors, otherwise a stack of matrices whose columns are solved independently.
+///The solution has the shape of `b` and the stack of `A` is broadcast to the stack of `b`.
+///Returns Error if any matrix is singular.
+///```
+///use rapl::*;
+///let a = Ndarr::from([[3., 1.], [1., 2.]]);
+///let b = Ndarr::from([9., 8.]);
+///let x = linalg::solve(&a, &b).unwrap();
+///assert!(x.data.iter().zip([2f64, 3.]).all(|(a, b)| (a - b).abs() < 1e-12));
+///let stack = Ndarr::from([[[2., 0.], [0., 2.]], [[1., 0.], [0., 4.]]]);
+///let x = linalg::solve(&stack, &Ndarr::from([[2., 2.], [1., 4.]])).unwrap();
+///assert_eq!(x, Ndarr::from([[1., 1.], [1., 1.]]));
+///```
+pub fn solve<T: LinalgScalar, R1: Unsigned, R2: Unsigned>(
+    a: &Ndarr<T, R1>,
+    b: &Ndarr<T, R2>,
+) -> Result<Ndarr<T, R2>, LinalgError> {
+    let (a_stack, factors) = map_stack(a, |m| {
+        lu_nonsingular(m, "Singular matrix, the system has no unique solution.")
+    })?;
+    let n = a.shape()[a.rank() - 1];
+    solve_stack(&a_stack, a.rank(), n, b, |p, rhs| Ok(factors[p].solve(rhs)))
+}
+
+//Solves a stack of systems, `a_stack` and `a_rank` are the stack shape and rank of the coefficient array of `n x n`
+//matrices. `solve` gets the position of the matrix in its stack and a right-hand side, which follows the rules of `solve`.
+pub(crate) fn solve_stack<T: LinalgScalar, R: Unsigned, F>(
+    a_stack: &[usize],
+    a_rank: usize,
+    n: usize,
+    b: &Ndarr<T, R>,
+    solve: F,
+) -> Result<Ndarr<T, R>, LinalgError>
+where
+    F: Fn(usize, &Mat<T>) -> Result<Mat<T>, LinalgError>,
+{
+    let core_rank = if b.rank() + 1 == a_rank { 1 } else { 2 };
+    if b.rank() < core_rank {
+        return Err(LinalgError::new(&format!(
+            "Right-hand side of shape {:?} is not compatible with a matrix with {} rows.",
+            b.shape(),
+            n
+        )));
+    }
+    let (b_stack, b_core) = b.shape().split_at(b.rank() - core_rank);
+    let (a_frame, b_frame) = (Dim::<UTerm>::new(a_stack)?, Dim::<UTerm>::new(b_stack)?);
+    if a_frame.broadcast_shape_notyped(&b_frame)?.shape != b_stack {
+        return Err(LinalgError::new(&format!(
+            "The stack of shape {:?} can not be broadcast to the stack of the right-hand side {:?}.",
+            a_stack, b_stack
+        )));
+    }
+    let block = multiply_list(b_core, 1);
+    let mut data = Vec::with_capacity(b.len());
+    for i in 0..multiply_list(b_stack, 1) {
+        let rhs = rhs_to_mat(
+            &Ndarr::<T, UTerm> {
+                data: b.data[i * block..(i + 1) * block].to_vec(),
+                dim: Dim::new(b_core)?,
+            },
+            n,
+        )?;
+        let p = Dim::<UTerm>::rev_cast_pos(&a_frame, &b_frame.get_indexes(&i))?;
+        data.extend(solve(p, &rhs)?.data);
+    }
+    Ok(Ndarr {
+        data,
+        dim: b.dim.clone(),
+    })
+}
+
+///Cholesky factor of every matrix of a stack, see the `cholesky` method for single matrices.
+pub fn cholesky<T: LinalgScalar, R: Unsigned>(
+    a: &Ndarr<T, R>,
+    triangle: Triangle,
+) -> Result<Ndarr<T, R>, LinalgError> {
+    let (stack, parts) = map_stack(a, |m| Ok(m.cholesky(triangle)?.data))?;
+    restack(&stack, &a.shape()[a.rank() - 2..], parts)
+}
+
+///Eigenvalues and eigenvectors of every symmetric (Hermitian) matrix of a stack, see the `eigh` method for single matrices.
+pub fn eigh<T: LinalgScalar, R>(a: &Ndarr<T, R>) -> EighResult<T, R>
+where
+    R: Unsigned + Sub<B1>,
+    Sub1<R>: Unsigned,
+{
+    let (stack, parts) = map_stack(a, |m| m.eigh())?;
+    let n = a.shape()[a.rank() - 1];
+    let (w, v): (Vec<Vec<T::Real>>, Vec<Vec<T>>) =
+        parts.into_iter().map(|(w, v)| (w.data, v.data)).unzip();
+    Ok((restack(&stack, &[n], w)?, restack(&stack, &[n, n], v)?))
+}
+
+///Eigenvalues and eigenvectors of every general real matrix of a stack, see the `eig` method for single matrices.
+#[cfg(feature = "complex")]
+pub fn eig<T, R>(a: &Ndarr<T, R>) -> EigResult<T, R>
+where
+    T: LinalgScalar<Real = T> + Float,
+    C<T>: LinalgScalar<Real = T>,
+    R: Unsigned + Sub<B1>,
+    Sub1<R>: Unsigned,
+{
+    let (stack, parts) = map_stack(a, |m| m.eig())?;
+    let n = a.shape()[a.rank() - 1];
+    let (w, v): (Vec<_>, Vec<_>) = parts.into_iter().map(|(w, v)| (w.data, v.data)).unzip();
+    Ok((restack(&stack, &[n], w)?, restack(&stack, &[n, n], v)?))
+}
+
+///Singular value decomposition of every matrix of a stack, see the `svd` method for single matrices.
+///```
+///use rapl::*;
+///let a = Ndarr::from([[[3., 0.], [0., 2.]], [[0., 1.], [5., 0.]]]);
+///let (u, s, vh) = linalg::svd(&a, false).unwrap();
+///assert_eq!(s, Ndarr::from([[3., 2.], [5., 1.]]));
+///assert_eq!((u.shape(), vh.shape()), (&[2, 2, 2][..], &[2, 2, 2][..]));
+///```
+pub fn svd<T: LinalgScalar, R>(a: &Ndarr<T, R>, full_matrices: bool) -> SvdResult<T, R>
+where
+    R: Unsigned + Sub<B1>,
+    Sub1<R>: Unsigned,
+{
+    let (stack, parts) = map_stack(a, |m| m.svd(full_matrices))?;
+    let (m, n) = (a.shape()[a.rank() - 2], a.shape()[a.rank() - 1]);
+    let k = m.min(n);
+    let (u_cols, v_rows) = if full_matrices { (m, n) } else { (k, k) };
+    let mut u_parts = Vec::with_capacity(parts.len());
+    let mut s_parts = Vec::with_capacity(parts.len());
+    let mut v_parts = Vec::with_capacity(parts.len());
+    for (u, s, vh) in parts {
+        u_parts.push(u.data);
+        s_parts.push(s.data);
+        v_parts.push(vh.data);
+    }
+    Ok((
+        restack(&stack, &[m, u_cols], u_parts)?,
+        restack(&stack, &[k], s_parts)?,
+        restack(&stack, &[v_rows, n], v_parts)?,
+    ))
+}
+
+#[cfg(test)]
+mod batched_test {
+    use super::*;
+
+    fn stack() -> Ndarr<f64, U3> {
+        Ndarr::from([
+            [[4., 1., 0.], [1., 3., 1.], [0., 1., 2.]],
+            [[2., -1., 0.], [-1., 2., -1.], [0., -1., 2.]],
+        ])
+    }
+
+    #[test]
+    fn det_inv_solve() {
+        let a = stack();
+        let d = det(&a).unwrap();
+        assert!(close(&d, &Ndarr::from([18., 4.]), 1e-12));
+        let (sign, log) = slogdet(&a).unwrap();
+        assert_eq!(sign, Ndarr::from([1., 1.]));
+        assert!(close(&log, &Ndarr::from([18f64.ln(), 4f64.ln()]), 1e-12));
+        let ai = inv(&a).unwrap();
+        for (m, mi) in a.slice_at(0).iter().zip(ai.slice_at(0)) {
+            assert!(close(&mi, &inv(m).unwrap(), 1e-14));
+        }
+        //stack of vectors, stack of matrices and a single matrix broadcast over a stack
+        let b = Ndarr::from([[1., 2., 3.], [0., 1., 0.]]);
+        let x = solve(&a, &b).unwrap();
+        assert!(close(
+            &x.slice_at(0)[1],
+            &solve(&a.slice_at(0)[1], &Ndarr::from([0., 1., 0.])).unwrap(),
+            1e-14
+        ));
+        let bm: Ndarr<f64, U3> = Ndarr::from([
+            [[1., 0.], [0., 1.], [2., 0.]],
+            [[0., 0.], [1., 1.], [0., 2.]],
+        ]);
+        let x = solve(&a, &bm).unwrap();
+        assert_eq!(x.shape(), &[2, 3, 2]);
+        let single = a.slice_at(0)[0].clone();
+        let x = solve(&single, &bm).unwrap();
+        assert!(close(
+            &x.slice_at(0)[1],
+            &solve(&single, &bm.slice_at(0)[1]).unwrap(),
+            1e-14
+        ));
+        assert!(solve(&a, &Ndarr::from([1., 2., 3.])).is_err());
+        let three: Ndarr<f64, U3> = Ndarr::from([
+            [[1., 0.], [0., 1.]],
+            [[1., 0.], [0., 1.]],
+            [[1., 0.], [0., 1.]],
+        ]);
+        assert!(solve(&three, &Ndarr::from([[1., 2.], [3., 4.]])).is_err());
+        assert!(det(&Ndarr::from([[1., 2.], [2., 4.]])).unwrap().data == vec![0.]);
+    }
+
+    #[test]
+    fn decompositions() {
+        let a = stack();
+        let l = cholesky(&a, Triangle::Lower).unwrap();
+        for (m, lm) in a.slice_at(0).iter().zip(l.slice_at(0)) {
+            assert!(close(&lm.mat_mul(&lm.t()), m, 1e-12));
+        }
+        let (w, v) = eigh(&a).unwrap();
+        assert_eq!((w.shape(), v.shape()), (&[2, 3][..], &[2, 3, 3][..]));
+        assert!(close(
+            &w.slice_at(0)[1],
+            &a.slice_at(0)[1].eigh().unwrap().0,
+            1e-14
+        ));
+        let (u, s, vh) = svd(&a, true).unwrap();
+        assert_eq!(
+            (u.shape(), s.shape(), vh.shape()),
+            (&[2, 3, 3][..], &[2, 3][..], &[2, 3, 3][..])
+        );
+        //singular values of a symmetric positive definite matrix are its eigenvalues
+        let mut sorted = w.slice_at(0)[0].data.clone();
+        sorted.reverse();
+        assert!(close(&s.slice_at(0)[0], &Ndarr::from(sorted), 1e-12));
+        let wide: Ndarr<f64, U4> = Ndarr::from([[[[1., 2., 3.], [4., 5., 6.]]]]);
+        let (u, s, vh) = svd(&wide, false).unwrap();
+        assert_eq!(
+            (u.shape(), s.shape(), vh.shape()),
+            (&[1, 1, 2, 2][..], &[1, 1, 2][..], &[1, 1, 2, 3][..])
+        );
+        assert!(inv(&Ndarr::from([1., 2.])).is_err());
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn eig_stack() {
+        let a: Ndarr<f64, U3> = Ndarr::from([[[0., -1.], [1., 0.]], [[2., 0.], [0., 3.]]]);
+        let (w, v) = eig(&a).unwrap();
+        assert_eq!(v.shape(), &[2, 2, 2]);
+        let expected = Ndarr::from([[C(0., -1.), C(0., 1.)], [C(2., 0.), C(3., 0.)]]);
+        assert!(close(&w, &expected, 1e-12));
+    }
+}
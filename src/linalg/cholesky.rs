@@ -0,0 +1,204 @@
+use super::*;
+
+///Triangle of a triangular factor or matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Triangle {
+    Lower,
+    Upper,
+}
+
+type LdlResult<T> = Result<(Ndarr<T, U2>, Ndarr<<T as LinalgScalar>::Real, U1>), LinalgError>;
+
+//Lower Cholesky factor `A = L L^H` of a Hermitian matrix.
+pub(crate) fn cholesky_lower<T: LinalgScalar>(a: &Mat<T>) -> Result<Mat<T>, LinalgError> {
+    let n = check_hermitian(a)?;
+    let mut l: Mat<T> = Mat::zeros(n, n);
+    for j in 0..n {
+        let d = (0..j).fold(a.get(j, j).re(), |s, k| s - l.get(j, k).modulus().powi(2));
+        if d <= T::Real::zero() || d.is_nan() {
+            return Err(LinalgError::new(&format!(
+                "Matrix is not positive definite, the leading minor of order {} is not positive.",
+                j + 1
+            )));
+        }
+        let d = d.sqrt();
+        l.set(j, j, T::from_real(d));
+        for i in j + 1..n {
+            let s = (0..j).fold(a.get(i, j), |s, k| s - l.get(i, k) * l.get(j, k).conj());
+            l.set(i, j, s / T::from_real(d));
+        }
+    }
+    Ok(l)
+}
+
+impl<T: LinalgScalar> Ndarr<T, U2> {
+    ///Cholesky factorization of a symmetric (Hermitian) positive definite matrix. With `Triangle::Lower` returns `L`
+    ///such that `A = L L^H`, with `Triangle::Upper` returns `U` such that `A = U^H U`.
+    ///Returns Error if the matrix is not Hermitian or not positive definite.
+    ///```
+    ///use rapl::*;
+    ///use rapl::linalg::Triangle;
+    ///let a = Ndarr::from([[4., 2.], [2., 5.]]);
+    ///let l = a.cholesky(Triangle::Lower).unwrap();
+    ///assert_eq!(l, Ndarr::from([[2., 0.], [1., 2.]]));
+    ///assert!(Ndarr::from([[1., 2.], [2., 1.]]).cholesky(Triangle::Lower).is_err());
+    ///```
+    pub fn cholesky(&self, triangle: Triangle) -> Result<Self, LinalgError> {
+        let l = cholesky_lower(&Mat::from_ndarr(self))?;
+        Ok(match triangle {
+            Triangle::Lower => l.into_ndarr(),
+            Triangle::Upper => l.adjoint().into_ndarr(),
+        })
+    }
+
+    ///`L D L^H` factorization of a symmetric (Hermitian) matrix, with `L` unit lower triangular and `D` real diagonal.
+    ///Unlike `cholesky` it works for positive semidefinite matrices, whose zero pivots give zero entries in `D`.
+    ///Returns `(L, D)` with `D` as a vector, or Error if a zero pivot is found in a column that is not zero.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1., 1.], [1., 1.]]);
+    ///let (l, d) = a.ldl().unwrap();
+    ///assert_eq!(l, Ndarr::from([[1., 0.], [1., 1.]]));
+    ///assert_eq!(d, Ndarr::from([1., 0.]));
+    ///```
+    pub fn ldl(&self) -> LdlResult<T> {
+        let a = Mat::from_ndarr(self);
+        let n = check_hermitian(&a)?;
+        let tol =
+            a.max_modulus() * T::Real::epsilon() * <T::Real as NumCast>::from(n.max(1)).unwrap();
+        let mut l: Mat<T> = Mat::identity(n);
+        let mut d: Vec<T::Real> = vec![T::Real::zero(); n];
+        for j in 0..n {
+            let dj = (0..j).fold(a.get(j, j).re(), |s, k| {
+                s - l.get(j, k).modulus().powi(2) * d[k]
+            });
+            let column: Vec<T> = (j + 1..n)
+                .map(|i| {
+                    (0..j).fold(a.get(i, j), |s, k| {
+                        s - l.get(i, k) * l.get(j, k).conj() * T::from_real(d[k])
+                    })
+                })
+                .collect();
+            if dj.abs() <= tol {
+                if column.iter().any(|x| x.modulus() > tol) {
+                    return Err(LinalgError::new(&format!(
+                        "Zero pivot at position {} in the LDL factorization.",
+                        j
+                    )));
+                }
+                continue;
+            }
+            d[j] = dj;
+            for (i, x) in column.into_iter().enumerate() {
+                l.set(j + 1 + i, j, x / T::from_real(dj));
+            }
+        }
+        Ok((
+            l.into_ndarr(),
+            Ndarr {
+                data: d,
+                dim: Dim::new(&[n])?,
+            },
+        ))
+    }
+}
+
+///Solves `A x = b` given the Cholesky factor of `A` computed by `cholesky`, and the triangle it was computed with.
+///The right-hand side `b` can be a vector or a matrix, in which case every column is solved independently.
+///```
+///use rapl::*;
+///use rapl::linalg::Triangle;
+///let a = Ndarr::from([[4., 2.], [2., 5.]]);
+///let l = a.cholesky(Triangle::Lower).unwrap();
+///let x = linalg::cho_solve(&l, Triangle::Lower, &Ndarr::from([8., 8.])).unwrap();
+///assert_eq!(x, Ndarr::from([1.5, 1.]));
+///```
+pub fn cho_solve<T: LinalgScalar, R: Unsigned>(
+    c: &Ndarr<T, U2>,
+    triangle: Triangle,
+    b: &Ndarr<T, R>,
+) -> Result<Ndarr<T, R>, LinalgError> {
+    let n = check_square(c)?;
+    let rhs = rhs_to_mat(b, n)?;
+    let c = Mat::from_ndarr(c);
+    let (l, u) = match triangle {
+        Triangle::Lower => (c.clone(), c.adjoint()),
+        Triangle::Upper => (c.adjoint(), c),
+    };
+    let y = forward_substitute(&l, &rhs);
+    Ok(Ndarr {
+        data: back_substitute(&u, &y, n).data,
+        dim: b.dim.clone(),
+    })
+}
+
+#[cfg(test)]
+mod cholesky_test {
+    use super::*;
+
+    #[test]
+    fn factors() {
+        let a: Ndarr<f64, U2> = Ndarr::from([[4., 12., -16.], [12., 37., -43.], [-16., -43., 98.]]);
+        let l = a.cholesky(Triangle::Lower).unwrap();
+        assert!(close(
+            &l,
+            &Ndarr::from([[2., 0., 0.], [6., 1., 0.], [-8., 5., 3.]]),
+            1e-12
+        ));
+        let u = a.cholesky(Triangle::Upper).unwrap();
+        assert!(close(&u.t().mat_mul(&u), &a, 1e-12));
+        let b = Ndarr::from([[1., 0.], [2., 1.], [3., -1.]]);
+        for (c, t) in [(l, Triangle::Lower), (u, Triangle::Upper)] {
+            let x = cho_solve(&c, t, &b).unwrap();
+            assert!(close(&a.mat_mul(&x), &b, 1e-10));
+        }
+    }
+
+    #[test]
+    fn not_positive_definite() {
+        let indefinite = Ndarr::from([[1., 2.], [2., 1.]]);
+        let err = indefinite.cholesky(Triangle::Lower).unwrap_err();
+        assert!(err.to_string().contains("not positive definite"));
+        let semidefinite = Ndarr::from([[1., 1.], [1., 1.]]);
+        assert!(semidefinite.cholesky(Triangle::Lower).is_err());
+        let non_symmetric = Ndarr::from([[2., 1.], [0., 2.]]);
+        assert!(non_symmetric.cholesky(Triangle::Lower).is_err());
+        assert!(Ndarr::from([[1., 0., 0.]])
+            .cholesky(Triangle::Upper)
+            .is_err());
+    }
+
+    #[test]
+    fn ldl() {
+        //gram matrix of rank 2
+        let v: Ndarr<f64, U2> = Ndarr::from([[1., 2.], [0., 1.], [1., 3.]]);
+        let a = v.mat_mul(&v.t());
+        let (l, d) = a.ldl().unwrap();
+        let dm = Ndarr::from(
+            (0..9)
+                .map(|i| if i % 4 == 0 { d.data[i / 4] } else { 0. })
+                .collect::<Vec<f64>>(),
+        )
+        .reshape([3, 3])
+        .unwrap();
+        assert!(close(&l.mat_mul(&dm).mat_mul(&l.t()), &a, 1e-12));
+        assert!(d.data.iter().all(|x| *x >= 0.));
+        assert!(d.data[2].abs() < 1e-12);
+        assert!(Ndarr::from([[0., 1.], [1., 0.]]).ldl().is_err());
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn hermitian() {
+        let a: Ndarr<C<f64>, U2> = Ndarr::from([[C(4., 0.), C(1., 1.)], [C(1., -1.), C(3., 0.)]]);
+        let l = a.cholesky(Triangle::Lower).unwrap();
+        let l_h = l.map(|z| z.conj()).t();
+        assert!(close(&l.mat_mul(&l_h), &a, 1e-12));
+        let b = Ndarr::from([C(1., 0.), C(0., 2.)]);
+        let x = cho_solve(&l, Triangle::Lower, &b).unwrap();
+        assert!(close(&a.mat_mul(&x), &b, 1e-12));
+        let (l, d) = a.ldl().unwrap();
+        assert!((d.data[0] - 4.).abs() < 1e-12 && (d.data[1] - 2.5).abs() < 1e-12);
+        assert!((l.data[2] - C(0.25, -0.25)).modulus() < 1e-12);
+    }
+}
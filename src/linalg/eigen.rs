@@ -0,0 +1,425 @@
+use super::*;
+use std::cmp::Ordering;
+
+#[cfg(feature = "complex")]
+use super::lu::lu_factor;
+
+type EighResult<T> = Result<(Ndarr<<T as LinalgScalar>::Real, U1>, Ndarr<T, U2>), LinalgError>;
+#[cfg(feature = "complex")]
+type EigResult<T> = Result<(Ndarr<C<T>, U1>, Ndarr<C<T>, U2>), LinalgError>;
+
+pub(crate) const MAX_SWEEPS: usize = 100;
+const MAX_QR_ITERATIONS: usize = 30;
+
+//Unitary rotation `J = [[jpp, jpq], [jqp, jqq]]` that diagonalizes the Hermitian block `[[app, apq], [conj(apq), aqq]]`
+//as `J^H B J`, for a non zero `apq`. It removes the phase of `apq` and then applies the classic real Jacobi rotation.
+pub(crate) fn jacobi_rotation<T: LinalgScalar>(app: T::Real, aqq: T::Real, apq: T) -> [T; 4] {
+    let one = T::Real::one();
+    let r = apq.modulus();
+    let phase = (apq / T::from_real(r)).conj();
+    let theta = (aqq - app) / ((one + one) * r);
+    let t = theta.signum() / (theta.abs() + (theta * theta + one).sqrt());
+    let c = one / (t * t + one).sqrt();
+    let s = t * c;
+    [
+        T::from_real(c),
+        T::from_real(s),
+        -T::from_real(s) * phase,
+        T::from_real(c) * phase,
+    ]
+}
+
+//Cyclic Jacobi eigenvalue algorithm for Hermitian matrices. Returns the eigenvalues in ascending
+//order and the eigenvectors as the columns of a unitary matrix.
+pub(crate) fn jacobi_eigh<T: LinalgScalar>(
+    a: &Mat<T>,
+) -> Result<(Vec<T::Real>, Mat<T>), LinalgError> {
+    let n = check_hermitian(a)?;
+    let mut a = a.clone();
+    let mut v: Mat<T> = Mat::identity(n);
+    let zero = T::Real::zero();
+    let eps = T::Real::epsilon();
+    let total = a.data.iter().fold(zero, |s, x| s + x.modulus().powi(2));
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let off = (0..n)
+            .flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| (i, j)))
+            .fold(zero, |s, (i, j)| s + a.get(i, j).modulus().powi(2));
+        if off <= eps * eps * total {
+            converged = true;
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a.get(p, q);
+                let r = apq.modulus();
+                if r == zero {
+                    continue;
+                }
+                let [jpp, jpq, jqp, jqq] = jacobi_rotation(a.get(p, p).re(), a.get(q, q).re(), apq);
+                for k in 0..n {
+                    let (x, y) = (a.get(k, p), a.get(k, q));
+                    a.set(k, p, x * jpp + y * jqp);
+                    a.set(k, q, x * jpq + y * jqq);
+                    let (x, y) = (v.get(k, p), v.get(k, q));
+                    v.set(k, p, x * jpp + y * jqp);
+                    v.set(k, q, x * jpq + y * jqq);
+                }
+                for k in 0..n {
+                    let (x, y) = (a.get(p, k), a.get(q, k));
+                    a.set(p, k, jpp.conj() * x + jqp.conj() * y);
+                    a.set(q, k, jpq.conj() * x + jqq.conj() * y);
+                }
+                a.set(p, q, T::zero());
+                a.set(q, p, T::zero());
+            }
+        }
+    }
+    if !converged {
+        return Err(LinalgError::new(
+            "The Jacobi eigenvalue iteration did not converge.",
+        ));
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|i, j| {
+        a.get(*i, *i)
+            .re()
+            .partial_cmp(&a.get(*j, *j).re())
+            .unwrap_or(Ordering::Equal)
+    });
+    let mut vectors = Mat::zeros(n, n);
+    for (new, old) in order.iter().enumerate() {
+        for k in 0..n {
+            vectors.set(k, new, v.get(k, *old));
+        }
+    }
+    Ok((order.iter().map(|i| a.get(*i, *i).re()).collect(), vectors))
+}
+
+//Reduces a square matrix to upper Hessenberg form with Householder reflections, `H = Q^H A Q`.
+#[cfg(feature = "complex")]
+fn hessenberg<T: LinalgScalar>(h: &mut Mat<T>) {
+    let n = h.rows;
+    for k in 0..n.saturating_sub(2) {
+        let (v, scale) = match householder((k + 1..n).map(|i| h.get(i, k)).collect()) {
+            Some(x) => x,
+            None => continue,
+        };
+        for j in k..n {
+            let dot = v
+                .iter()
+                .enumerate()
+                .fold(T::zero(), |s, (i, vi)| s + vi.conj() * h.get(k + 1 + i, j));
+            for (i, vi) in v.iter().enumerate() {
+                h.set(k + 1 + i, j, h.get(k + 1 + i, j) - scale * *vi * dot);
+            }
+        }
+        for i in 0..n {
+            let dot = v
+                .iter()
+                .enumerate()
+                .fold(T::zero(), |s, (l, vl)| s + h.get(i, k + 1 + l) * *vl);
+            for (l, vl) in v.iter().enumerate() {
+                h.set(i, k + 1 + l, h.get(i, k + 1 + l) - scale * dot * vl.conj());
+            }
+        }
+        for i in k + 2..n {
+            h.set(i, k, T::zero());
+        }
+    }
+}
+
+//Eigenvalues of an upper Hessenberg matrix by the shifted QR algorithm in complex arithmetic, with Wilkinson shifts,
+//deflation of negligible subdiagonal elements and an exceptional shift every ten iterations without deflation.
+#[cfg(feature = "complex")]
+fn hessenberg_eigenvalues<T: LinalgScalar>(mut h: Mat<T>) -> Result<Vec<T>, LinalgError> {
+    let n = h.rows;
+    let eps = T::Real::epsilon();
+    let half = T::from_real(T::Real::one() / (T::Real::one() + T::Real::one()));
+    let scale = h.max_modulus();
+    let mut hi = n.saturating_sub(1);
+    let mut iter = 0;
+    while hi > 0 {
+        let mut l = hi;
+        while l > 0 {
+            let mut s = h.get(l - 1, l - 1).modulus() + h.get(l, l).modulus();
+            if s == T::Real::zero() {
+                s = scale;
+            }
+            if h.get(l, l - 1).modulus() <= eps * s {
+                h.set(l, l - 1, T::zero());
+                break;
+            }
+            l -= 1;
+        }
+        if l == hi {
+            hi -= 1;
+            iter = 0;
+            continue;
+        }
+        iter += 1;
+        if iter > MAX_QR_ITERATIONS * n {
+            return Err(LinalgError::new(
+                "The QR eigenvalue iteration did not converge.",
+            ));
+        }
+        let (a, b) = (h.get(hi - 1, hi - 1), h.get(hi - 1, hi));
+        let (c, d) = (h.get(hi, hi - 1), h.get(hi, hi));
+        let mu = if iter % 10 == 0 {
+            d + T::from_real(c.modulus())
+        } else {
+            let mean = (a + d) * half;
+            let root = ((a - d) * half * (a - d) * half + b * c).principal_sqrt();
+            let (mu1, mu2) = (mean + root, mean - root);
+            if (mu1 - d).modulus() <= (mu2 - d).modulus() {
+                mu1
+            } else {
+                mu2
+            }
+        };
+        for k in l..=hi {
+            h.set(k, k, h.get(k, k) - mu);
+        }
+        //H - mu I = Q R with Givens rotations, then H <- R Q + mu I
+        let mut rotations = Vec::with_capacity(hi - l);
+        for k in l..hi {
+            let (x, y) = (h.get(k, k), h.get(k + 1, k));
+            let r = (x.modulus().powi(2) + y.modulus().powi(2)).sqrt();
+            let (c, s) = if r == T::Real::zero() {
+                (T::one(), T::zero())
+            } else {
+                (x / T::from_real(r), y / T::from_real(r))
+            };
+            for j in k..=hi {
+                let (p, q) = (h.get(k, j), h.get(k + 1, j));
+                h.set(k, j, c.conj() * p + s.conj() * q);
+                h.set(k + 1, j, -s * p + c * q);
+            }
+            rotations.push((c, s));
+        }
+        for (k, (c, s)) in (l..hi).zip(rotations) {
+            for i in l..=k + 1 {
+                let (p, q) = (h.get(i, k), h.get(i, k + 1));
+                h.set(i, k, p * c + q * s);
+                h.set(i, k + 1, -p * s.conj() + q * c.conj());
+            }
+        }
+        for k in l..=hi {
+            h.set(k, k, h.get(k, k) + mu);
+        }
+    }
+    Ok((0..n).map(|i| h.get(i, i)).collect())
+}
+
+impl<T: LinalgScalar> Ndarr<T, U2> {
+    ///Eigenvalues and eigenvectors of a symmetric (Hermitian) matrix, computed with the Jacobi eigenvalue algorithm.
+    ///Returns the real eigenvalues in ascending order and a matrix whose orthonormal columns are the eigenvectors.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[2f64, 1.], [1., 2.]]);
+    ///let (w, v) = a.eigh().unwrap();
+    ///assert!((w.data[0] - 1.).abs() < 1e-12 && (w.data[1] - 3.).abs() < 1e-12);
+    ///assert_eq!(v.shape(), &[2, 2]);
+    ///```
+    pub fn eigh(&self) -> EighResult<T> {
+        let (values, vectors) = jacobi_eigh(&Mat::from_ndarr(self))?;
+        Ok((
+            Ndarr {
+                dim: Dim::new(&[values.len()])?,
+                data: values,
+            },
+            vectors.into_ndarr(),
+        ))
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T> Ndarr<T, U2>
+where
+    T: LinalgScalar<Real = T> + Float,
+    C<T>: LinalgScalar<Real = T>,
+{
+    ///Eigenvalues and eigenvectors of a general real matrix. The matrix is reduced to Hessenberg form and its eigenvalues
+    ///are found with the shifted QR algorithm, then every eigenvector is computed by inverse iteration.
+    ///Returns the complex eigenvalues sorted by real and then imaginary part, and a matrix whose columns are the
+    ///corresponding unit norm eigenvectors.
+    ///```
+    ///use rapl::*;
+    ///let rotation = Ndarr::from([[0., -1.], [1., 0.]]);
+    ///let (w, v) = rotation.eig().unwrap();
+    ///assert!((w.data[0] - C(0., -1.)).abs() < 1e-12);
+    ///assert!((w.data[1] - C(0., 1.)).abs() < 1e-12);
+    ///assert_eq!(v.shape(), &[2, 2]);
+    ///```
+    pub fn eig(&self) -> EigResult<T> {
+        let n = check_square(self)?;
+        let a = Mat {
+            rows: n,
+            cols: n,
+            data: self.data.iter().map(|x| C(*x, T::zero())).collect(),
+        };
+        let mut h = a.clone();
+        hessenberg(&mut h);
+        let mut values = hessenberg_eigenvalues(h)?;
+        let scale = a.max_modulus().max(T::min_positive_value());
+        let tol = scale * T::epsilon() * <T as NumCast>::from(n.max(1)).unwrap();
+        //the imaginary part of real eigenvalues is only rounding error
+        for w in values.iter_mut() {
+            if w.1.abs() <= tol {
+                w.1 = T::zero();
+            }
+        }
+        values.sort_by(|x, y| {
+            (x.0, x.1)
+                .partial_cmp(&(y.0, y.1))
+                .unwrap_or(Ordering::Equal)
+        });
+        //inverse iteration with a slightly perturbed shift, starting from a different vector for every eigenvalue
+        //so that repeated eigenvalues of diagonalizable matrices get independent eigenvectors
+        //absolute floor so that the shift and the pivots do not vanish for the zero matrix
+        let unit = T::epsilon() * a.max_modulus().max(T::one());
+        let delta = C::from_real(unit * <T as NumCast>::from(n.max(1)).unwrap());
+        let start = C::from_real(<T as NumCast>::from(0.1).unwrap());
+        let mut vectors = Mat::zeros(n, n);
+        for (j, w) in values.iter().enumerate() {
+            let mut shifted = a.clone();
+            for i in 0..n {
+                shifted.set(i, i, a.get(i, i) - *w - delta);
+            }
+            let mut f = lu_factor(&shifted);
+            //as in LAPACK's inverse iteration, exactly singular pivots are replaced by a tiny value
+            for i in 0..n {
+                if f.lu.get(i, i) == C::zero() {
+                    f.lu.set(i, i, C::from_real(unit));
+                }
+            }
+            let mut x = Mat {
+                rows: n,
+                cols: 1,
+                data: (0..n)
+                    .map(|i| if i == j { C::one() } else { start })
+                    .collect(),
+            };
+            for _ in 0..2 {
+                x = f.solve(&x);
+                let norm = x
+                    .data
+                    .iter()
+                    .fold(T::zero(), |s, e| s + e.modulus().powi(2))
+                    .sqrt();
+                if !norm.is_finite() || norm == T::zero() {
+                    return Err(LinalgError::new(
+                        "The inverse iteration for the eigenvectors did not converge.",
+                    ));
+                }
+                x.data.iter_mut().for_each(|e| *e /= C::from_real(norm));
+            }
+            for i in 0..n {
+                vectors.set(i, j, x.data[i]);
+            }
+        }
+        Ok((
+            Ndarr {
+                dim: Dim::new(&[n])?,
+                data: values,
+            },
+            vectors.into_ndarr(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod eigen_test {
+    use super::*;
+
+    #[test]
+    fn eigh_real() {
+        let a: Ndarr<f64, U2> = Ndarr::from([[2., -1., 0.], [-1., 2., -1.], [0., -1., 2.]]);
+        let (w, v) = a.eigh().unwrap();
+        let s = 2f64.sqrt();
+        assert!(close(&w, &Ndarr::from([2. - s, 2., 2. + s]), 1e-12));
+        let id = Ndarr::from([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
+        assert!(close(&v.t().mat_mul(&v), &id, 1e-12));
+        for j in 0..3 {
+            let col = Ndarr::from((0..3).map(|i| v.data[i * 3 + j]).collect::<Vec<f64>>());
+            assert!(close(&a.mat_mul(&col), &(col.clone() * w.data[j]), 1e-12));
+        }
+        assert!(Ndarr::from([[1., 2.], [0., 1.]]).eigh().is_err());
+        let (w, _) = Ndarr::from([[5., 0.], [0., -1.]]).eigh().unwrap();
+        assert_eq!(w, Ndarr::from([-1., 5.]));
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn eigh_hermitian() {
+        let a: Ndarr<C<f64>, U2> = Ndarr::from([[C(2., 0.), C(0., 1.)], [C(0., -1.), C(2., 0.)]]);
+        let (w, v) = a.eigh().unwrap();
+        assert!(close(&w, &Ndarr::from([1., 3.]), 1e-12));
+        for j in 0..2 {
+            let col = Ndarr::from(vec![v.data[j], v.data[2 + j]]);
+            let lambda = C(w.data[j], 0.);
+            let av = a.mat_mul(&col);
+            assert!(close(&av, &col.map(|z| *z * lambda), 1e-12));
+        }
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn eig_general() {
+        //checks A v = w v for every eigenpair and unit norm eigenvectors
+        fn check(a: &Ndarr<f64, U2>, w: &Ndarr<C<f64>, U1>, v: &Ndarr<C<f64>, U2>) {
+            let n = a.shape()[0];
+            let ac = a.map(|x| C(*x, 0.));
+            for j in 0..n {
+                let col = Ndarr::from((0..n).map(|i| v.data[i * n + j]).collect::<Vec<C<f64>>>());
+                let norm: f64 = col.data.iter().map(|z| z.modulus().powi(2)).sum();
+                assert!((norm - 1.).abs() < 1e-10);
+                let lambda = w.data[j];
+                assert!(close(&ac.mat_mul(&col), &col.map(|z| *z * lambda), 1e-8));
+            }
+        }
+        let a: Ndarr<f64, U2> = Ndarr::from([
+            [4., 1., -2., 2.],
+            [1., 2., 0., 1.],
+            [-2., 0., 3., -2.],
+            [2., 1., -2., -1.],
+        ]);
+        let (w, v) = a.eig().unwrap();
+        check(&a, &w, &v);
+        assert!(w.data.iter().all(|z| z.1 == 0.));
+        //companion matrix of x^3 - 1, roots are the cube roots of unity
+        let b = Ndarr::from([[0., 0., 1.], [1., 0., 0.], [0., 1., 0.]]);
+        let (w, v) = b.eig().unwrap();
+        check(&b, &w, &v);
+        let h = 3f64.sqrt() / 2.;
+        let expected = Ndarr::from([C(-0.5, -h), C(-0.5, h), C(1., 0.)]);
+        assert!(close(&w, &expected, 1e-10));
+        //repeated eigenvalue with independent eigenvectors
+        let id = Ndarr::from([[2., 0.], [0., 2.]]);
+        let (w, v) = id.eig().unwrap();
+        check(&id, &w, &v);
+        let det = v.data[0] * v.data[3] - v.data[1] * v.data[2];
+        assert!(det.modulus() > 1e-3);
+        //larger non symmetric matrix and a defective Jordan block
+        let c = Ndarr::from(
+            (0..64)
+                .map(|k| ((k * 7 + k / 8 * 3) % 11) as f64 - 5.)
+                .collect::<Vec<f64>>(),
+        )
+        .reshape([8, 8])
+        .unwrap();
+        let (w, v) = c.eig().unwrap();
+        check(&c, &w, &v);
+        let jordan = Ndarr::from([[1., 1.], [0., 1.]]);
+        let (w, _) = jordan.eig().unwrap();
+        assert!(close(&w, &Ndarr::from([C(1., 0.), C(1., 0.)]), 1e-8));
+        assert!(Ndarr::from([[1., 2., 3.]]).eig().is_err());
+        //zero matrices, every vector is an eigenvector
+        for n in [2, 3] {
+            let zero: Ndarr<f64, U2> = Ndarr::zeros([n, n]);
+            let (w, v) = zero.eig().unwrap();
+            check(&zero, &w, &v);
+            assert!(w.data.iter().all(|z| *z == C(0., 0.)));
+        }
+    }
+}
@@ -0,0 +1,478 @@
+use super::*;
+use crate::sparse::{CscMatrix, CsrMatrix};
+
+type IterResult<T> = Result<(Ndarr<T, U1>, Convergence<<T as LinalgScalar>::Real>), LinalgError>;
+
+///Linear map on vectors, the only thing the iterative methods need from a matrix is its action on a vector.
+///It is implemented for dense matrices, sparse matrices and closures `Fn(&Ndarr<T, U1>) -> Ndarr<T, U1>`.
+///```
+///use rapl::*;
+///use rapl::linalg::LinearOperator;
+///let double = |x: &Ndarr<f64, U1>| x.clone() * 2.;
+///assert_eq!(double.apply(&Ndarr::from([1., 2.])).unwrap(), Ndarr::from([2., 4.]));
+///let a = Ndarr::from([[1., 1.], [0., 1.]]);
+///assert_eq!(a.apply(&Ndarr::from([1., 2.])).unwrap(), Ndarr::from([3., 2.]));
+///assert!(a.apply(&Ndarr::from([1., 2., 3.])).is_err());
+///```
+pub trait LinearOperator<T: Clone> {
+    ///Product of the operator with the vector `x`. Returns Error if the length of `x` does not match the operator.
+    fn apply(&self, x: &Ndarr<T, U1>) -> Result<Ndarr<T, U1>, LinalgError>;
+}
+
+impl<T: LinalgScalar> LinearOperator<T> for Ndarr<T, U2> {
+    fn apply(&self, x: &Ndarr<T, U1>) -> Result<Ndarr<T, U1>, LinalgError> {
+        if self.shape()[1] != x.len() {
+            return Err(LinalgError::new(&format!(
+                "Matrix of shape {:?} can not be applied to a vector of length {}.",
+                self.shape(),
+                x.len()
+            )));
+        }
+        Ok(self.mat_mul(x))
+    }
+}
+
+impl<T: LinalgScalar> LinearOperator<T> for CsrMatrix<T> {
+    fn apply(&self, x: &Ndarr<T, U1>) -> Result<Ndarr<T, U1>, LinalgError> {
+        Ok(self.mat_mul(x)?)
+    }
+}
+
+impl<T: LinalgScalar> LinearOperator<T> for CscMatrix<T> {
+    fn apply(&self, x: &Ndarr<T, U1>) -> Result<Ndarr<T, U1>, LinalgError> {
+        Ok(self.mat_mul(x)?)
+    }
+}
+
+impl<T: Clone, F: Fn(&Ndarr<T, U1>) -> Ndarr<T, U1>> LinearOperator<T> for F {
+    fn apply(&self, x: &Ndarr<T, U1>) -> Result<Ndarr<T, U1>, LinalgError> {
+        Ok(self(x))
+    }
+}
+
+///Stopping criteria of the iterative methods: the iteration stops once the relative residual is below `tol`,
+///or after `max_iter` iterations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IterOptions<F> {
+    ///Tolerance on the relative residual.
+    pub tol: F,
+    ///Maximum number of iterations, for the eigensolvers the maximum dimension of the Krylov subspace.
+    pub max_iter: usize,
+}
+
+impl<F: Float> IterOptions<F> {
+    ///Options with tolerance `tol` and at most `max_iter` iterations.
+    pub fn new(tol: F, max_iter: usize) -> Self {
+        IterOptions { tol, max_iter }
+    }
+}
+
+impl<F: Float> Default for IterOptions<F> {
+    ///Tolerance of the square root of the machine epsilon and at most 1000 iterations.
+    fn default() -> Self {
+        IterOptions::new(F::epsilon().sqrt(), 1000)
+    }
+}
+
+///Convergence report of an iterative method. `residual` is the final residual norm, relative to the norm of the
+///right-hand side for linear systems and to the largest Ritz value for eigenvalue problems, and `converged` tells if
+///it is below the tolerance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Convergence<F> {
+    ///Whether the residual is below the tolerance.
+    pub converged: bool,
+    ///Number of iterations performed.
+    pub iterations: usize,
+    ///Final relative residual norm.
+    pub residual: F,
+}
+
+//Inner product `x^H y`.
+pub(crate) fn dot<T: LinalgScalar>(x: &[T], y: &[T]) -> T {
+    x.iter()
+        .zip(y)
+        .fold(T::zero(), |s, (a, b)| s + a.conj() * *b)
+}
+
+pub(crate) fn norm2<T: LinalgScalar>(x: &[T]) -> T::Real {
+    x.iter()
+        .fold(T::Real::zero(), |s, a| s + a.modulus().powi(2))
+        .sqrt()
+}
+
+//`y += a x`
+pub(crate) fn axpy<T: LinalgScalar>(y: &mut [T], a: T, x: &[T]) {
+    for (y, x) in y.iter_mut().zip(x) {
+        *y = *y + a * *x;
+    }
+}
+
+//Applies the operator to a vector, checking that the result has the same length.
+pub(crate) fn apply<T: LinalgScalar, A: LinearOperator<T> + ?Sized>(
+    a: &A,
+    x: &[T],
+) -> Result<Vec<T>, LinalgError> {
+    let y = a.apply(&Ndarr {
+        data: x.to_vec(),
+        dim: Dim::new(&[x.len()])?,
+    })?;
+    if y.len() != x.len() {
+        return Err(LinalgError::new(&format!(
+            "The operator returned a vector of length {} for a vector of length {}.",
+            y.len(),
+            x.len()
+        )));
+    }
+    Ok(y.data)
+}
+
+//Initial guess, zero by default, and the norm of the right-hand side.
+fn setup<T: LinalgScalar>(
+    b: &Ndarr<T, U1>,
+    x0: Option<&Ndarr<T, U1>>,
+) -> Result<(Vec<T>, T::Real), LinalgError> {
+    let x = match x0 {
+        Some(x0) if x0.len() != b.len() => {
+            return Err(LinalgError::new(&format!(
+                "Initial guess of length {} for a right-hand side of length {}.",
+                x0.len(),
+                b.len()
+            )))
+        }
+        Some(x0) => x0.data.clone(),
+        None => vec![T::zero(); b.len()],
+    };
+    Ok((x, norm2(&b.data)))
+}
+
+fn residual<T: LinalgScalar, A: LinearOperator<T>>(
+    a: &A,
+    b: &Ndarr<T, U1>,
+    x: &[T],
+) -> Result<Vec<T>, LinalgError> {
+    let ax = apply(a, x)?;
+    Ok(b.data.iter().zip(ax).map(|(b, ax)| *b - ax).collect())
+}
+
+//Solution and convergence report with the true residual of `x`.
+fn report<T: LinalgScalar, A: LinearOperator<T>>(
+    a: &A,
+    b: &Ndarr<T, U1>,
+    x: Vec<T>,
+    iterations: usize,
+    options: IterOptions<T::Real>,
+) -> IterResult<T> {
+    let bnorm = norm2(&b.data);
+    let residual = if bnorm == T::Real::zero() {
+        norm2(&x)
+    } else {
+        norm2(&residual(a, b, &x)?) / bnorm
+    };
+    Ok((
+        Ndarr {
+            dim: Dim::new(&[x.len()])?,
+            data: x,
+        },
+        Convergence {
+            converged: residual <= options.tol,
+            iterations,
+            residual,
+        },
+    ))
+}
+
+///Solves `A x = b` with the Conjugate Gradient method, `A` must be symmetric (Hermitian) positive definite.
+///The iteration starts from `x0`, zero by default, and the convergence report holds the true relative residual.
+///```
+///use rapl::*;
+///use rapl::linalg::{cg, IterOptions};
+///let a = Ndarr::from([[4., 1.], [1., 3.]]);
+///let (x, report) = cg(&a, &Ndarr::from([1., 2.]), None, IterOptions::new(1e-10, 100)).unwrap();
+///assert!(report.converged);
+///assert!((x - Ndarr::from([1. / 11., 7. / 11.])).data.iter().all(|e: &f64| e.abs() < 1e-9));
+///```
+pub fn cg<T: LinalgScalar, A: LinearOperator<T>>(
+    a: &A,
+    b: &Ndarr<T, U1>,
+    x0: Option<&Ndarr<T, U1>>,
+    options: IterOptions<T::Real>,
+) -> IterResult<T> {
+    let (mut x, bnorm) = setup(b, x0)?;
+    if bnorm == T::Real::zero() {
+        return report(a, b, vec![T::zero(); b.len()], 0, options);
+    }
+    let mut r = residual(a, b, &x)?;
+    let mut p = r.clone();
+    let mut rs = norm2(&r).powi(2);
+    let mut iterations = 0;
+    while iterations < options.max_iter && rs.sqrt() > options.tol * bnorm {
+        let ap = apply(a, &p)?;
+        let pap = dot(&p, &ap);
+        if pap.modulus() == T::Real::zero() {
+            break;
+        }
+        let alpha = T::from_real(rs) / pap;
+        axpy(&mut x, alpha, &p);
+        axpy(&mut r, -alpha, &ap);
+        let rs_next = norm2(&r).powi(2);
+        let beta = T::from_real(rs_next / rs);
+        for (p, r) in p.iter_mut().zip(&r) {
+            *p = *r + beta * *p;
+        }
+        rs = rs_next;
+        iterations += 1;
+    }
+    report(a, b, x, iterations, options)
+}
+
+///Solves `A x = b` for a general square `A` with the Biconjugate Gradient Stabilized method.
+///The iteration starts from `x0`, zero by default, and the convergence report holds the true relative residual.
+pub fn bicgstab<T: LinalgScalar, A: LinearOperator<T>>(
+    a: &A,
+    b: &Ndarr<T, U1>,
+    x0: Option<&Ndarr<T, U1>>,
+    options: IterOptions<T::Real>,
+) -> IterResult<T> {
+    let (mut x, bnorm) = setup(b, x0)?;
+    if bnorm == T::Real::zero() {
+        return report(a, b, vec![T::zero(); b.len()], 0, options);
+    }
+    let n = b.len();
+    let mut r = residual(a, b, &x)?;
+    let r_hat = r.clone();
+    let (mut rho, mut alpha, mut omega) = (T::one(), T::one(), T::one());
+    let (mut v, mut p) = (vec![T::zero(); n], vec![T::zero(); n]);
+    let mut iterations = 0;
+    while iterations < options.max_iter && norm2(&r) > options.tol * bnorm {
+        let rho_next = dot(&r_hat, &r);
+        if rho_next.modulus() == T::Real::zero() || omega.modulus() == T::Real::zero() {
+            break;
+        }
+        let beta = (rho_next / rho) * (alpha / omega);
+        for ((p, r), v) in p.iter_mut().zip(&r).zip(&v) {
+            *p = *r + beta * (*p - omega * *v);
+        }
+        v = apply(a, &p)?;
+        let r_hat_v = dot(&r_hat, &v);
+        if r_hat_v.modulus() == T::Real::zero() {
+            break;
+        }
+        alpha = rho_next / r_hat_v;
+        rho = rho_next;
+        axpy(&mut x, alpha, &p);
+        axpy(&mut r, -alpha, &v);
+        iterations += 1;
+        if norm2(&r) <= options.tol * bnorm {
+            break;
+        }
+        let t = apply(a, &r)?;
+        let tt = norm2(&t).powi(2);
+        omega = if tt == T::Real::zero() {
+            T::zero()
+        } else {
+            dot(&t, &r) / T::from_real(tt)
+        };
+        axpy(&mut x, omega, &r);
+        axpy(&mut r, -omega, &t);
+    }
+    report(a, b, x, iterations, options)
+}
+
+///Solves `A x = b` for a general square `A` with the Generalized Minimal Residual method, restarted every `restart`
+///iterations to bound the size of the Krylov basis. Every application of `A` counts as an iteration.
+///The iteration starts from `x0`, zero by default, and the convergence report holds the true relative residual.
+///```
+///use rapl::*;
+///use rapl::linalg::{gmres, IterOptions};
+///let a = Ndarr::from([[2., 1., 0.], [0., 3., 1.], [1., 0., 4.]]);
+///let b = Ndarr::from([3., 4., 5.]);
+///let (x, report) = gmres(&a, &b, None, 10, IterOptions::new(1e-10, 100)).unwrap();
+///assert!(report.converged && report.iterations <= 3);
+///assert!(x.data.iter().all(|e: &f64| (e - 1.).abs() < 1e-9));
+///```
+pub fn gmres<T: LinalgScalar, A: LinearOperator<T>>(
+    a: &A,
+    b: &Ndarr<T, U1>,
+    x0: Option<&Ndarr<T, U1>>,
+    restart: usize,
+    options: IterOptions<T::Real>,
+) -> IterResult<T> {
+    let (mut x, bnorm) = setup(b, x0)?;
+    if bnorm == T::Real::zero() {
+        return report(a, b, vec![T::zero(); b.len()], 0, options);
+    }
+    let zero = T::Real::zero();
+    let mut iterations = 0;
+    'restart: while iterations < options.max_iter {
+        let r = residual(a, b, &x)?;
+        let beta = norm2(&r);
+        if beta <= options.tol * bnorm {
+            break;
+        }
+        let mut basis = vec![r
+            .iter()
+            .map(|e| *e / T::from_real(beta))
+            .collect::<Vec<T>>()];
+        //columns of the Hessenberg matrix, reduced to upper triangular by Givens rotations `(c, s)`
+        let mut h: Vec<Vec<T>> = Vec::new();
+        let mut rotations: Vec<(T::Real, T)> = Vec::new();
+        let mut g = vec![T::from_real(beta)];
+        for j in 0..restart.max(1) {
+            let mut w = apply(a, &basis[j])?;
+            let mut col = Vec::with_capacity(j + 2);
+            for v in &basis {
+                let hij = dot(v, &w);
+                axpy(&mut w, -hij, v);
+                col.push(hij);
+            }
+            let h_next = norm2(&w);
+            col.push(T::from_real(h_next));
+            for (i, (c, s)) in rotations.iter().enumerate() {
+                let (a0, a1) = (col[i], col[i + 1]);
+                col[i] = T::from_real(*c) * a0 + *s * a1;
+                col[i + 1] = T::from_real(*c) * a1 - s.conj() * a0;
+            }
+            let (a0, a1) = (col[j], col[j + 1]);
+            let d = (a0.modulus().powi(2) + a1.modulus().powi(2)).sqrt();
+            let (c, s) = if d == zero {
+                (T::Real::one(), T::zero())
+            } else if a0.modulus() == zero {
+                (zero, a1.conj() / T::from_real(a1.modulus()))
+            } else {
+                (
+                    a0.modulus() / d,
+                    a0 / T::from_real(a0.modulus()) * a1.conj() / T::from_real(d),
+                )
+            };
+            col[j] = T::from_real(c) * a0 + s * a1;
+            col[j + 1] = T::zero();
+            g.push(-s.conj() * g[j]);
+            g[j] = T::from_real(c) * g[j];
+            rotations.push((c, s));
+            h.push(col);
+            iterations += 1;
+            if g[j + 1].modulus() <= options.tol * bnorm
+                || h_next == zero
+                || iterations == options.max_iter
+            {
+                break;
+            }
+            basis.push(w.iter().map(|e| *e / T::from_real(h_next)).collect());
+        }
+        let k = h.len();
+        let mut y = vec![T::zero(); k];
+        for i in (0..k).rev() {
+            if h[i][i].modulus() == zero {
+                //singular least squares problem, the operator is singular
+                break 'restart;
+            }
+            let mut s = g[i];
+            for j in i + 1..k {
+                s = s - h[j][i] * y[j];
+            }
+            y[i] = s / h[i][i];
+        }
+        for (y, v) in y.iter().zip(&basis) {
+            axpy(&mut x, *y, v);
+        }
+    }
+    report(a, b, x, iterations, options)
+}
+
+#[cfg(test)]
+mod iterative_test {
+    use super::*;
+
+    //1D Poisson matrix with Dirichlet boundaries, symmetric positive definite
+    fn poisson(n: usize) -> CsrMatrix<f64> {
+        let mut a = crate::sparse::CooMatrix::new([n, n]);
+        for i in 0..n {
+            a.push(i, i, 2.).unwrap();
+            if i + 1 < n {
+                a.push(i, i + 1, -1.).unwrap();
+                a.push(i + 1, i, -1.).unwrap();
+            }
+        }
+        a.to_csr()
+    }
+
+    #[test]
+    fn conjugate_gradient() {
+        let a = poisson(50);
+        let b: Ndarr<f64, U1> = Ndarr::from(vec![1.; 50]);
+        let options = IterOptions::new(1e-10, 200);
+        let (x, report) = cg(&a, &b, None, options).unwrap();
+        assert!(report.converged && report.residual <= 1e-10);
+        //exact arithmetic converges in n steps at most
+        assert!(report.iterations <= 50);
+        assert!(close(&x, &solve(&a.to_dense(), &b).unwrap(), 1e-8));
+        let (_, report) = cg(&a, &b, None, IterOptions::new(1e-10, 5)).unwrap();
+        assert!(!report.converged && report.iterations == 5);
+        let (_, report) = cg(&a, &b, Some(&x), options).unwrap();
+        assert!(report.converged && report.iterations <= 1);
+        let (x, report) = cg(&a, &Ndarr::from(vec![0.; 50]), None, options).unwrap();
+        assert!(report.converged && x.data.iter().all(|e| *e == 0.));
+        assert!(cg(&a, &b, Some(&Ndarr::from([1.])), options).is_err());
+        let wrong = |x: &Ndarr<f64, U1>| Ndarr::from(vec![0.; x.len() + 1]);
+        assert!(cg(&wrong, &b, None, options).is_err());
+        //operators whose size does not match the right-hand side
+        let short = Ndarr::from([1., 2.]);
+        let dense = a.to_dense();
+        assert!(cg(&dense, &short, None, options).is_err());
+        assert!(cg(&a, &short, None, options).is_err());
+        assert!(bicgstab(&a.to_csc(), &short, None, options).is_err());
+        assert!(gmres(&dense, &short, None, 10, options).is_err());
+    }
+
+    #[test]
+    fn nonsymmetric() {
+        //convection diffusion matrix
+        let n = 40;
+        let a = poisson(n).to_dense()
+            + Ndarr::from(
+                (0..n * n)
+                    .map(|p| if p % n == p / n + 1 { 0.5 } else { 0. })
+                    .collect::<Vec<f64>>(),
+            )
+            .reshape([n, n])
+            .unwrap();
+        let b = Ndarr::from((0..n).map(|i| i as f64).collect::<Vec<f64>>());
+        let options = IterOptions::new(1e-10, 500);
+        let expected = solve(&a, &b).unwrap();
+        let (x, report) = bicgstab(&a, &b, None, options).unwrap();
+        assert!(report.converged);
+        assert!(close(&x, &expected, 1e-7));
+        let (x, report) = gmres(&a, &b, None, n, options).unwrap();
+        assert!(report.converged && report.iterations <= n);
+        assert!(close(&x, &expected, 1e-7));
+        let (x, report) = gmres(&a, &b, None, 10, options).unwrap();
+        //at least one restart
+        assert!(report.converged && report.iterations > 10);
+        assert!(close(&x, &expected, 1e-7));
+        //closure operator
+        let op = |v: &Ndarr<f64, U1>| a.mat_mul(v);
+        let (y, _) = gmres(&op, &b, None, n, options).unwrap();
+        assert!(close(&x, &y, 1e-7));
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn complex() {
+        let a: Ndarr<C<f64>, U2> = Ndarr::from([
+            [C(4., 0.), C(1., 1.), C(0., 0.)],
+            [C(1., -1.), C(3., 0.), C(0., 2.)],
+            [C(0., 0.), C(0., -2.), C(5., 0.)],
+        ]);
+        let b = Ndarr::from([C(1., 0.), C(0., 1.), C(2., -1.)]);
+        let expected = solve(&a, &b).unwrap();
+        let options = IterOptions::new(1e-12, 100);
+        for (x, report) in [
+            cg(&a, &b, None, options).unwrap(),
+            bicgstab(&a, &b, None, options).unwrap(),
+            gmres(&a, &b, None, 3, options).unwrap(),
+        ] {
+            assert!(report.converged);
+            assert!(close(&x, &expected, 1e-10));
+        }
+    }
+}
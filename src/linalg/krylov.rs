@@ -0,0 +1,339 @@
+use super::eigen::jacobi_eigh;
+use super::iterative::{apply, axpy, dot, norm2};
+use super::*;
+#[cfg(feature = "complex")]
+use crate::helpers::nan_last_cmp;
+use crate::utils::random::NdarrRand;
+
+type LanczosResult<T> = Result<
+    (
+        Ndarr<<T as LinalgScalar>::Real, U1>,
+        Ndarr<T, U2>,
+        Convergence<<T as LinalgScalar>::Real>,
+    ),
+    LinalgError,
+>;
+#[cfg(feature = "complex")]
+type ArnoldiResult<T> = Result<(Ndarr<C<T>, U1>, Ndarr<C<T>, U2>, Convergence<T>), LinalgError>;
+
+///Part of the spectrum searched by `lanczos` and `arnoldi`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Which {
+    ///Largest eigenvalues, in algebraic value for `lanczos` and in modulus for `arnoldi`.
+    Largest,
+    ///Smallest eigenvalues, in algebraic value for `lanczos` and in modulus for `arnoldi`.
+    Smallest,
+}
+
+//Random unit vector orthogonal to `basis`, the seed makes the methods deterministic.
+fn start_vector<T: LinalgScalar>(n: usize, basis: &[Vec<T>], seed: u64) -> Vec<T> {
+    let random: Ndarr<f64, U1> = NdarrRand::uniform(-1., 1., [n], Some(seed));
+    let mut v: Vec<T> = random
+        .data
+        .iter()
+        .map(|x| T::from_real(<T::Real as NumCast>::from(*x).unwrap()))
+        .collect();
+    orthogonalize(&mut v, basis);
+    let norm = norm2(&v);
+    v.iter().map(|x| *x / T::from_real(norm)).collect()
+}
+
+//Removes the components along the orthonormal `basis` with two passes of Gram-Schmidt, returns the coefficients.
+fn orthogonalize<T: LinalgScalar>(w: &mut [T], basis: &[Vec<T>]) -> Vec<T> {
+    let mut coefficients = vec![T::zero(); basis.len()];
+    for _ in 0..2 {
+        for (c, v) in coefficients.iter_mut().zip(basis) {
+            let h = dot(v, w);
+            axpy(w, -h, v);
+            *c = *c + h;
+        }
+    }
+    coefficients
+}
+
+//Computing the Ritz pairs costs O(m³), so once there are `k` of them the convergence is checked every few steps,
+//at the last step and when the basis spans an invariant subspace.
+fn check_due(m: usize, k: usize, max_dim: usize, breakdown: bool) -> bool {
+    m >= k && (m == max_dim || breakdown || (m - k).is_multiple_of(k.max(10)))
+}
+
+fn check_wanted(n: usize, k: usize) -> Result<(), LinalgError> {
+    if k == 0 || k > n {
+        return Err(LinalgError::new(&format!(
+            "Expected between 1 and {} eigenpairs, found {}.",
+            n, k
+        )));
+    }
+    Ok(())
+}
+
+///`k` extreme eigenvalues and eigenvectors of a symmetric (Hermitian) operator of size `n`, computed with the Lanczos
+///method with full reorthogonalization. The Krylov basis grows until the Ritz pairs converge or its size reaches
+///`options.max_iter` or `n`. Returns the eigenvalues, descending for `Which::Largest` and ascending for
+///`Which::Smallest`, the unit eigenvectors as columns and a convergence report.
+///```
+///use rapl::*;
+///use rapl::linalg::{lanczos, IterOptions, Which};
+///let a = Ndarr::from([[2f64, 1., 0.], [1., 2., 1.], [0., 1., 2.]]);
+///let (w, v, report) = lanczos(&a, 3, 1, Which::Largest, IterOptions::default()).unwrap();
+///assert!(report.converged);
+///assert!((w.data[0] - (2. + 2f64.sqrt())).abs() < 1e-8);
+///```
+pub fn lanczos<T: LinalgScalar, A: LinearOperator<T>>(
+    a: &A,
+    n: usize,
+    k: usize,
+    which: Which,
+    options: IterOptions<T::Real>,
+) -> LanczosResult<T> {
+    check_wanted(n, k)?;
+    let max_dim = options.max_iter.max(k).min(n);
+    let mut basis = vec![start_vector::<T>(n, &[], 0)];
+    let (mut alpha, mut beta): (Vec<T::Real>, Vec<T::Real>) = (Vec::new(), Vec::new());
+    loop {
+        let m = basis.len();
+        let mut w = apply(a, &basis[m - 1])?;
+        alpha.push(dot(&basis[m - 1], &w).re());
+        orthogonalize(&mut w, &basis);
+        let b = norm2(&w);
+        let scale = alpha.iter().chain(&beta).fold(b, |s, x| s.max(x.abs()));
+        let breakdown = b <= T::Real::epsilon() * scale;
+        if check_due(m, k, max_dim, breakdown) {
+            let mut t = Mat::zeros(m, m);
+            for i in 0..m {
+                t.set(i, i, alpha[i]);
+                if i + 1 < m {
+                    t.set(i, i + 1, beta[i]);
+                    t.set(i + 1, i, beta[i]);
+                }
+            }
+            let (theta, s) = jacobi_eigh(&t)?;
+            let wanted: Vec<usize> = match which {
+                Which::Largest => (m - k..m).rev().collect(),
+                Which::Smallest => (0..k).collect(),
+            };
+            //the residual of a Ritz pair is `b |s[m - 1, i]|`
+            let converged = wanted
+                .iter()
+                .all(|i| b * s.get(m - 1, *i).abs() <= options.tol * scale);
+            if converged || m == max_dim {
+                let mut values = Vec::with_capacity(k);
+                let mut vectors = Mat::zeros(n, k);
+                let mut residual = T::Real::zero();
+                for (c, i) in wanted.into_iter().enumerate() {
+                    let mut x = vec![T::zero(); n];
+                    for (l, v) in basis.iter().enumerate() {
+                        axpy(&mut x, T::from_real(s.get(l, i)), v);
+                    }
+                    let norm = norm2(&x);
+                    x.iter_mut().for_each(|e| *e = *e / T::from_real(norm));
+                    let mut r = apply(a, &x)?;
+                    axpy(&mut r, -T::from_real(theta[i]), &x);
+                    residual = residual.max(norm2(&r));
+                    for (l, e) in x.into_iter().enumerate() {
+                        vectors.set(l, c, e);
+                    }
+                    values.push(theta[i]);
+                }
+                let scale = theta.iter().fold(T::Real::zero(), |s, x| s.max(x.abs()));
+                if scale > T::Real::zero() {
+                    residual = residual / scale;
+                }
+                return Ok((
+                    Ndarr {
+                        data: values,
+                        dim: Dim::new(&[k])?,
+                    },
+                    vectors.into_ndarr(),
+                    Convergence {
+                        converged: residual <= options.tol,
+                        iterations: m,
+                        residual,
+                    },
+                ));
+            }
+        }
+        if breakdown {
+            //invariant subspace, continue with a new direction
+            beta.push(T::Real::zero());
+            basis.push(start_vector(n, &basis, m as u64));
+        } else {
+            beta.push(b);
+            basis.push(w.iter().map(|e| *e / T::from_real(b)).collect());
+        }
+    }
+}
+
+///`k` extreme eigenvalues and eigenvectors of a general real operator of size `n`, computed with the Arnoldi method.
+///The Krylov basis grows until the Ritz pairs converge or its size reaches `options.max_iter` or `n`.
+///Returns the complex eigenvalues sorted by descending modulus for `Which::Largest` and ascending modulus for
+///`Which::Smallest`, the unit eigenvectors as columns and a convergence report.
+///```
+///use rapl::*;
+///use rapl::linalg::{arnoldi, IterOptions, Which};
+///let a = Ndarr::from([[0f64, -2., 0.], [2., 0., 0.], [0., 0., 1.]]);
+///let (w, v, report) = arnoldi(&a, 3, 2, Which::Largest, IterOptions::default()).unwrap();
+///assert!(report.converged);
+///assert!(w.data.iter().all(|z| (z.abs() - 2.).abs() < 1e-8));
+///```
+#[cfg(feature = "complex")]
+pub fn arnoldi<T, A: LinearOperator<T>>(
+    a: &A,
+    n: usize,
+    k: usize,
+    which: Which,
+    options: IterOptions<T>,
+) -> ArnoldiResult<T>
+where
+    T: LinalgScalar<Real = T> + Float,
+    C<T>: LinalgScalar<Real = T>,
+{
+    check_wanted(n, k)?;
+    let max_dim = options.max_iter.max(k).min(n);
+    let mut basis = vec![start_vector::<T>(n, &[], 0)];
+    //columns of the Hessenberg matrix
+    let mut h: Vec<Vec<T>> = Vec::new();
+    loop {
+        let m = basis.len();
+        let mut w = apply(a, &basis[m - 1])?;
+        let mut col = orthogonalize(&mut w, &basis);
+        let b = norm2(&w);
+        col.push(b);
+        h.push(col);
+        let scale = h.iter().flatten().fold(T::zero(), |s, x| s.max(x.abs()));
+        let breakdown = b <= T::epsilon() * scale;
+        if check_due(m, k, max_dim, breakdown) {
+            let mut hm = Mat::zeros(m, m);
+            for (j, col) in h.iter().enumerate() {
+                for (i, e) in col.iter().enumerate().take(m) {
+                    hm.set(i, j, *e);
+                }
+            }
+            let (theta, y) = hm.into_ndarr().eig()?;
+            let mut order: Vec<usize> = (0..m).collect();
+            order
+                .sort_by(|i, j| nan_last_cmp(&theta.data[*i].modulus(), &theta.data[*j].modulus()));
+            let wanted: Vec<usize> = match which {
+                Which::Largest => order.into_iter().rev().take(k).collect(),
+                Which::Smallest => order.into_iter().take(k).collect(),
+            };
+            let converged = wanted
+                .iter()
+                .all(|i| b * y.data[(m - 1) * m + *i].modulus() <= options.tol * scale);
+            if converged || m == max_dim {
+                let mut vectors = Mat::zeros(n, k);
+                let mut residual = T::zero();
+                for (c, i) in wanted.iter().enumerate() {
+                    let mut x = vec![C(T::zero(), T::zero()); n];
+                    for (l, v) in basis.iter().enumerate() {
+                        let v: Vec<C<T>> = v.iter().map(|e| C(*e, T::zero())).collect();
+                        axpy(&mut x, y.data[l * m + *i], &v);
+                    }
+                    let norm = norm2(&x);
+                    x.iter_mut().for_each(|e| *e /= C(norm, T::zero()));
+                    //the operator acts on the real and imaginary parts separately
+                    let re = apply(a, &x.iter().map(|e| e.0).collect::<Vec<T>>())?;
+                    let im = apply(a, &x.iter().map(|e| e.1).collect::<Vec<T>>())?;
+                    let mut r: Vec<C<T>> = re.into_iter().zip(im).map(|(r, i)| C(r, i)).collect();
+                    axpy(&mut r, -theta.data[*i], &x);
+                    residual = residual.max(norm2(&r));
+                    for (l, e) in x.into_iter().enumerate() {
+                        vectors.set(l, c, e);
+                    }
+                }
+                let scale = theta.data.iter().fold(T::zero(), |s, x| s.max(x.modulus()));
+                if scale > T::zero() {
+                    residual = residual / scale;
+                }
+                return Ok((
+                    Ndarr {
+                        data: wanted.iter().map(|i| theta.data[*i]).collect(),
+                        dim: Dim::new(&[k])?,
+                    },
+                    vectors.into_ndarr(),
+                    Convergence {
+                        converged: residual <= options.tol,
+                        iterations: m,
+                        residual,
+                    },
+                ));
+            }
+        }
+        if breakdown {
+            //invariant subspace, continue with a new direction
+            *h[m - 1].last_mut().unwrap() = T::zero();
+            basis.push(start_vector(n, &basis, m as u64));
+        } else {
+            basis.push(w.iter().map(|e| *e / b).collect());
+        }
+    }
+}
+
+#[cfg(test)]
+mod krylov_test {
+    use super::*;
+    use crate::sparse::CooMatrix;
+
+    #[test]
+    fn symmetric() {
+        //Laplacian of a path with fixed ends, eigenvalues 2 - 2 cos(pi j / (n + 1))
+        let n = 30;
+        let mut coo = CooMatrix::new([n, n]);
+        for i in 0..n {
+            coo.push(i, i, 2.).unwrap();
+            if i + 1 < n {
+                coo.push(i, i + 1, -1.).unwrap();
+                coo.push(i + 1, i, -1.).unwrap();
+            }
+        }
+        let a = coo.to_csr();
+        let options = IterOptions::new(1e-10, n);
+        let (w, v, report) = lanczos(&a, n, 3, Which::Smallest, options).unwrap();
+        assert!(report.converged && report.residual <= 1e-10);
+        let theta = std::f64::consts::PI / (n + 1) as f64;
+        let expected: Ndarr<f64, U1> =
+            Ndarr::from([1., 2., 3.]).map(|j| 2. - 2. * (j * theta).cos());
+        assert!(close(&w, &expected, 1e-8));
+        assert_eq!(v.shape(), &[n, 3]);
+        let (w, _, _) = lanczos::<f64, _>(&a, n, 1, Which::Largest, options).unwrap();
+        assert!((w.data[0] - 2. - 2. * theta.cos()).abs() < 1e-8);
+        //a tiny basis does not converge
+        let (_, _, report) =
+            lanczos(&a, n, 2, Which::Smallest, IterOptions::new(1e-10, 3)).unwrap();
+        assert!(!report.converged && report.iterations == 3);
+        assert!(lanczos(&a, n, 0, Which::Largest, options).is_err());
+        assert!(lanczos(&a, n - 1, 2, Which::Largest, options).is_err());
+        assert!(lanczos(&a.to_dense(), n + 1, 2, Which::Largest, options).is_err());
+        //the identity has an invariant Krylov space of dimension 1
+        let id = |x: &Ndarr<f64, U1>| x.clone();
+        let (w, _, report) = lanczos(&id, 5, 2, Which::Largest, options).unwrap();
+        assert!(report.converged && close(&w, &Ndarr::from([1., 1.]), 1e-12));
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn general() {
+        //upper triangular with known eigenvalues 1..=n
+        let n = 12;
+        let a = Ndarr::from(
+            (0..n * n)
+                .map(|p| match (p / n, p % n) {
+                    (i, j) if i == j => (i + 1) as f64,
+                    (i, j) if j > i => 0.5,
+                    _ => 0.,
+                })
+                .collect::<Vec<f64>>(),
+        )
+        .reshape([n, n])
+        .unwrap();
+        let options = IterOptions::new(1e-9, n);
+        let (w, v, report) = arnoldi(&a, n, 2, Which::Largest, options).unwrap();
+        assert!(report.converged);
+        assert!(close(&w, &Ndarr::from([C(12., 0.), C(11., 0.)]), 1e-8));
+        assert_eq!(v.shape(), &[n, 2]);
+        let (w, _, _) = arnoldi(&a, n, 1, Which::Smallest, options).unwrap();
+        assert!(close(&w, &Ndarr::from([C(1., 0.)]), 1e-8));
+        assert!(arnoldi(&a, n, n + 1, Which::Smallest, options).is_err());
+        assert!(arnoldi(&a, n - 1, 1, Which::Smallest, options).is_err());
+    }
+}
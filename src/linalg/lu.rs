@@ -0,0 +1,236 @@
+use super::*;
+
+//Compact LU factorization with partial pivoting, `P A = L U` where `L` has a unit diagonal.
+//`lu` stores `L` below the diagonal and `U` on and above it, `perm[i]` is the row of `A` at position `i`.
+pub(crate) struct LuFactor<T> {
+    pub lu: Mat<T>,
+    pub perm: Vec<usize>,
+    pub odd: bool,
+    pub singular: bool,
+}
+
+pub(crate) fn lu_factor<T: LinalgScalar>(a: &Mat<T>) -> LuFactor<T> {
+    let n = a.rows;
+    let mut lu = a.clone();
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut odd = false;
+    let mut singular = false;
+    for k in 0..n {
+        //pivot with the largest modulus in the column
+        let p = (k..n)
+            .max_by(|i, j| {
+                lu.get(*i, k)
+                    .modulus()
+                    .partial_cmp(&lu.get(*j, k).modulus())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap();
+        if p != k {
+            lu.swap_rows(p, k);
+            perm.swap(p, k);
+            odd = !odd;
+        }
+        let pivot = lu.get(k, k);
+        //only an exactly zero or non-finite pivot makes the system unsolvable, a small pivot may just be a badly scaled row
+        if pivot == T::zero() || !pivot.modulus().is_finite() {
+            singular = true;
+            if pivot == T::zero() {
+                continue;
+            }
+        }
+        for i in k + 1..n {
+            let l = lu.get(i, k) / pivot;
+            lu.set(i, k, l);
+            for j in k + 1..n {
+                lu.set(i, j, lu.get(i, j) - l * lu.get(k, j));
+            }
+        }
+    }
+    LuFactor {
+        lu,
+        perm,
+        odd,
+        singular,
+    }
+}
+
+impl<T: LinalgScalar> LuFactor<T> {
+    //Solves `A X = B` using the factorization.
+    pub fn solve(&self, b: &Mat<T>) -> Mat<T> {
+        let n = self.lu.rows;
+        let mut x = Mat::zeros(n, b.cols);
+        for (i, p) in self.perm.iter().enumerate() {
+            for j in 0..b.cols {
+                x.set(i, j, b.get(*p, j));
+            }
+        }
+        for j in 0..b.cols {
+            for i in 0..n {
+                let mut s = x.get(i, j);
+                for k in 0..i {
+                    s = s - self.lu.get(i, k) * x.get(k, j);
+                }
+                x.set(i, j, s);
+            }
+            for i in (0..n).rev() {
+                let mut s = x.get(i, j);
+                for k in i + 1..n {
+                    s = s - self.lu.get(i, k) * x.get(k, j);
+                }
+                x.set(i, j, s / self.lu.get(i, i));
+            }
+        }
+        x
+    }
+
+    pub fn det(&self) -> T {
+        let n = self.lu.rows;
+        let d = (0..n).fold(T::one(), |d, i| d * self.lu.get(i, i));
+        if self.odd {
+            -d
+        } else {
+            d
+        }
+    }
+}
+
+impl<T: LinalgScalar> Ndarr<T, U2> {
+    ///LU decomposition with partial pivoting. Returns `(P, L, U)` such that `A = P L U`, where `P` is a permutation matrix,
+    ///`L` is lower triangular with unit diagonal and `U` is upper triangular.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1., 2.], [3., 4.]]);
+    ///let (p, l, u) = a.lu().unwrap();
+    ///assert_eq!(p.mat_mul(&l).mat_mul(&u), a);
+    ///```
+    pub fn lu(&self) -> Result<(Self, Self, Self), LinalgError> {
+        let n = check_square(self)?;
+        let f = lu_factor(&Mat::from_ndarr(self));
+        let mut p = Mat::zeros(n, n);
+        let mut l = Mat::identity(n);
+        let mut u = Mat::zeros(n, n);
+        for i in 0..n {
+            p.set(f.perm[i], i, T::one());
+            for j in 0..n {
+                if j < i {
+                    l.set(i, j, f.lu.get(i, j));
+                } else {
+                    u.set(i, j, f.lu.get(i, j));
+                }
+            }
+        }
+        Ok((p.into_ndarr(), l.into_ndarr(), u.into_ndarr()))
+    }
+
+    ///Determinant of a square matrix.
+    pub fn det(&self) -> Result<T, LinalgError> {
+        check_square(self)?;
+        Ok(lu_factor(&Mat::from_ndarr(self)).det())
+    }
+
+    ///Sign and natural logarithm of the absolute value of the determinant, which does not overflow for large matrices.
+    ///The sign is `±1` for real matrices and a complex number of modulus one for complex matrices.
+    ///For singular matrices returns `(0, -inf)`.
+    pub fn slogdet(&self) -> Result<(T, T::Real), LinalgError> {
+        let n = check_square(self)?;
+        let f = lu_factor(&Mat::from_ndarr(self));
+        let mut sign = if f.odd { -T::one() } else { T::one() };
+        let mut logdet = T::Real::zero();
+        for i in 0..n {
+            let d = f.lu.get(i, i);
+            let m = d.modulus();
+            if m == T::Real::zero() {
+                return Ok((T::zero(), T::Real::neg_infinity()));
+            }
+            sign = sign * (d / T::from_real(m));
+            logdet = logdet + m.ln();
+        }
+        Ok((sign, logdet))
+    }
+}
+
+//LU factorization of a square matrix, Error if it is singular.
+pub(crate) fn lu_nonsingular<T: LinalgScalar>(
+    a: &Ndarr<T, U2>,
+    err: &str,
+) -> Result<LuFactor<T>, LinalgError> {
+    check_square(a)?;
+    let f = lu_factor(&Mat::from_ndarr(a));
+    if f.singular {
+        return Err(LinalgError::new(err));
+    }
+    Ok(f)
+}
+
+//Inverse of a single square matrix, `linalg::inv` applies it to every matrix of a stack.
+pub(crate) fn inv<T: LinalgScalar>(a: &Ndarr<T, U2>) -> Result<Ndarr<T, U2>, LinalgError> {
+    let f = lu_nonsingular(a, "Singular matrix, the inverse does not exist.")?;
+    Ok(f.solve(&Mat::identity(a.shape()[0])).into_ndarr())
+}
+
+#[cfg(test)]
+mod lu_test {
+    use super::*;
+
+    #[test]
+    fn lu_det_inv() {
+        let a: Ndarr<f64, U2> = Ndarr::from([[2., -1., 0.], [-1., 2., -1.], [0., -1., 2.]]);
+        let (p, l, u) = a.lu().unwrap();
+        assert!(close(&p.mat_mul(&l).mat_mul(&u), &a, 1e-12));
+        assert!((a.det().unwrap() - 4.).abs() < 1e-12);
+        let (sign, logdet) = a.slogdet().unwrap();
+        assert_eq!(sign, 1.);
+        assert!((logdet - 4f64.ln()).abs() < 1e-12);
+        let inv = inv(&a).unwrap();
+        let id = Ndarr::from([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
+        assert!(close(&a.mat_mul(&inv), &id, 1e-12));
+        let swap = Ndarr::from([[0., 1.], [1., 0.]]);
+        assert_eq!(swap.det().unwrap(), -1.);
+        assert_eq!(swap.slogdet().unwrap(), (-1., 0.));
+    }
+
+    #[test]
+    fn singular() {
+        let a = Ndarr::from([[1., 2.], [2., 4.]]);
+        assert_eq!(a.det().unwrap(), 0.);
+        assert_eq!(a.slogdet().unwrap().1, f64::NEG_INFINITY);
+        assert!(inv(&a).is_err());
+        assert!(solve(&a, &Ndarr::from([1., 1.])).is_err());
+        assert!(Ndarr::from([[1., 2., 3.]]).det().is_err());
+        //a badly scaled but nonsingular system
+        let a = Ndarr::from([[1e-20, 0.], [0., 1.]]);
+        assert_eq!(
+            solve(&a, &Ndarr::from([1e-20, 1.])).unwrap(),
+            Ndarr::from([1., 1.])
+        );
+        assert_eq!(inv(&a).unwrap(), Ndarr::from([[1e20, 0.], [0., 1.]]));
+    }
+
+    #[test]
+    fn solve_rhs() {
+        let a = Ndarr::from([[4., 3.], [6., 3.]]);
+        let x = solve(&a, &Ndarr::from([10., 12.])).unwrap();
+        assert!(close(&x, &Ndarr::from([1., 2.]), 1e-12));
+        let b = Ndarr::from([[10., 7.], [12., 9.]]);
+        let x = solve(&a, &b).unwrap();
+        assert!(close(&a.mat_mul(&x), &b, 1e-12));
+        assert!(solve(&a, &Ndarr::from([1., 2., 3.])).is_err());
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn complex() {
+        let a: Ndarr<C<f64>, U2> = Ndarr::from([[C(1., 1.), C(2., 0.)], [C(0., -1.), C(3., 2.)]]);
+        let det = a.det().unwrap();
+        //(1+i)(3+2i) - 2(-i) = 1 + 7i
+        assert!((det - C(1., 7.)).modulus() < 1e-12);
+        let b = Ndarr::from([C(1., 0.), C(0., 1.)]);
+        let x = solve(&a, &b).unwrap();
+        assert!(close(&a.mat_mul(&x), &b, 1e-12));
+        let (sign, logdet) = a.slogdet().unwrap();
+        assert!((sign * C(logdet.exp(), 0.) - det).modulus() < 1e-12);
+        let inv = inv(&a).unwrap();
+        let id = Ndarr::from([[C(1., 0.), C(0., 0.)], [C(0., 0.), C(1., 0.)]]);
+        assert!(close(&inv.mat_mul(&a), &id, 1e-12));
+    }
+}
@@ -0,0 +1,292 @@
+use super::lu::{lu_factor, LuFactor};
+use super::*;
+
+const MAX_SQRT_ITERATIONS: usize = 100;
+const MAX_LOG_SQUARE_ROOTS: i32 = 64;
+
+impl<T: LinalgScalar> Mat<T> {
+    //Maximum absolute column sum.
+    fn norm_one(&self) -> T::Real {
+        (0..self.cols)
+            .map(|j| (0..self.rows).fold(T::Real::zero(), |s, i| s + self.get(i, j).modulus()))
+            .fold(T::Real::zero(), |m, x| m.max(x))
+    }
+
+    fn scale(&self, x: T) -> Self {
+        Mat {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(|e| *e * x).collect(),
+        }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Mat {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| *a + *b)
+                .collect(),
+        }
+    }
+
+    //Solves `self X = B`, returning Error if `self` is singular.
+    fn left_divide(&self, b: &Self) -> Result<Self, LinalgError> {
+        let f = lu_factor(self);
+        if f.singular {
+            return Err(LinalgError::new(
+                "Singular matrix found while evaluating a matrix function.",
+            ));
+        }
+        Ok(f.solve(b))
+    }
+}
+
+fn real<T: LinalgScalar>(x: f64) -> T {
+    T::from_real(<T::Real as NumCast>::from(x).unwrap())
+}
+
+//Logarithm of the absolute value of the determinant from the LU factors, unlike `det` it does not overflow.
+fn ln_abs_det<T: LinalgScalar>(f: &LuFactor<T>) -> T::Real {
+    (0..f.lu.rows).fold(T::Real::zero(), |s, i| s + f.lu.get(i, i).modulus().ln())
+}
+
+fn is_finite<T: LinalgScalar>(a: &Mat<T>) -> bool {
+    a.data.iter().all(|x| x.modulus().is_finite())
+}
+
+//Principal square root by the Denman-Beavers iteration with determinant scaling.
+fn sqrt_mat<T: LinalgScalar>(a: &Mat<T>) -> Result<Mat<T>, LinalgError> {
+    let n = a.rows;
+    let half: T = real(0.5);
+    let tol = T::Real::epsilon() * <T::Real as NumCast>::from(n.max(1) * 10).unwrap();
+    let mut y = a.clone();
+    let mut z = Mat::identity(n);
+    for _ in 0..MAX_SQRT_ITERATIONS {
+        let (fy, fz) = (lu_factor(&y), lu_factor(&z));
+        if fy.singular || fz.singular {
+            return Err(LinalgError::new(
+                "Singular matrix found while computing the square root.",
+            ));
+        }
+        //mu = |det(Y) det(Z)|^(-1/2n), evaluated in log space
+        let log_det = ln_abs_det(&fy) + ln_abs_det(&fz);
+        let mu = (-log_det / <T::Real as NumCast>::from(2 * n).unwrap()).exp();
+        let (mu, mu_inv) = (T::from_real(mu), T::from_real(mu.recip()));
+        let identity = Mat::identity(n);
+        let y_next = y
+            .scale(mu)
+            .add(&fz.solve(&identity).scale(mu_inv))
+            .scale(half);
+        let z_next = z
+            .scale(mu)
+            .add(&fy.solve(&identity).scale(mu_inv))
+            .scale(half);
+        if !is_finite(&y_next) || !is_finite(&z_next) {
+            return Err(LinalgError::new("The square root iteration overflowed."));
+        }
+        let change = y_next.add(&y.scale(-T::one())).norm_one();
+        y = y_next;
+        z = z_next;
+        if change <= tol * y.norm_one() {
+            return Ok(y);
+        }
+    }
+    Err(LinalgError::new(
+        "The square root iteration did not converge.",
+    ))
+}
+
+impl<T: LinalgScalar> Ndarr<T, U2> {
+    ///Integer power of a square matrix by repeated squaring. Negative powers invert the matrix first.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1., 1.], [1., 0.]]);
+    ///assert_eq!(a.matrix_power(10).unwrap(), Ndarr::from([[89., 55.], [55., 34.]]));
+    ///```
+    pub fn matrix_power(&self, n: i32) -> Result<Self, LinalgError> {
+        let size = check_square(self)?;
+        let mut base = if n < 0 { inv(self)? } else { self.clone() };
+        let mut result = Mat::identity(size).into_ndarr();
+        let mut exp = n.unsigned_abs();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mat_mul(&base);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.mat_mul(&base);
+            }
+        }
+        Ok(result)
+    }
+
+    ///Matrix exponential, computed with a `[6/6]` Padé approximant and scaling and squaring.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[0., 1.], [0., 0.]]);
+    ///assert_eq!(a.expm().unwrap(), Ndarr::from([[1., 1.], [0., 1.]]));
+    ///```
+    pub fn expm(&self) -> Result<Self, LinalgError> {
+        let n = check_square(self)?;
+        let a = Mat::from_ndarr(self);
+        //scale so that |A / 2^s| <= 1/2
+        let norm = a.norm_one();
+        let half = T::Real::one() / (T::Real::one() + T::Real::one());
+        let mut s = 0;
+        while norm * half.powi(s) > half {
+            s += 1;
+        }
+        let a = a.scale(T::from_real(half.powi(s)));
+        let q = 6;
+        let mut c = 0.5;
+        let mut x = a.clone();
+        let mut num = Mat::identity(n).add(&a.scale(real(c)));
+        let mut den = Mat::identity(n).add(&a.scale(real(-c)));
+        for k in 2..=q {
+            c *= (q - k + 1) as f64 / (k * (2 * q - k + 1)) as f64;
+            x = a.matmul(&x);
+            num = num.add(&x.scale(real(c)));
+            den = den.add(&x.scale(real(if k % 2 == 0 { c } else { -c })));
+        }
+        let mut f = den.left_divide(&num)?;
+        for _ in 0..s {
+            f = f.matmul(&f);
+        }
+        Ok(f.into_ndarr())
+    }
+
+    ///Principal square root of a square matrix, computed with the scaled Denman-Beavers iteration.
+    ///The matrix must not have eigenvalues on the closed negative real axis.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[4., 0.], [0., 9.]]);
+    ///let s = a.sqrtm().unwrap();
+    ///assert!((s.mat_mul(&s) - a).abs().data.iter().all(|x: &f64| *x < 1e-12));
+    ///```
+    pub fn sqrtm(&self) -> Result<Self, LinalgError> {
+        check_square(self)?;
+        Ok(sqrt_mat(&Mat::from_ndarr(self))?.into_ndarr())
+    }
+
+    ///Principal logarithm of a square matrix, computed by inverse scaling and squaring: square roots are taken until
+    ///the matrix is close to the identity and the logarithm is then evaluated with the Gregory series.
+    ///The matrix must not have eigenvalues on the closed negative real axis.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1., 2.], [0., 1.]]);
+    ///let l = a.logm().unwrap();
+    ///assert!((l.expm().unwrap() - a).abs().data.iter().all(|x: &f64| *x < 1e-12));
+    ///```
+    pub fn logm(&self) -> Result<Self, LinalgError> {
+        let n = check_square(self)?;
+        let identity: Mat<T> = Mat::identity(n);
+        let quarter = <T::Real as NumCast>::from(0.25).unwrap();
+        let mut a = Mat::from_ndarr(self);
+        let mut k = 0;
+        while a.add(&identity.scale(-T::one())).norm_one() > quarter {
+            if k == MAX_LOG_SQUARE_ROOTS {
+                return Err(LinalgError::new("The logarithm scaling did not converge."));
+            }
+            a = sqrt_mat(&a)?;
+            k += 1;
+        }
+        //log(A) = 2 sum Y^(2j + 1) / (2j + 1), with Y = (A + I)^-1 (A - I), both factors commute
+        let y = a
+            .add(&identity)
+            .left_divide(&a.add(&identity.scale(-T::one())))?;
+        let y2 = y.matmul(&y);
+        let mut term = y.clone();
+        let mut sum = y;
+        let tol = T::Real::epsilon();
+        for j in 1..100 {
+            term = term.matmul(&y2);
+            let next = term.scale(real(1. / (2 * j + 1) as f64));
+            sum = sum.add(&next);
+            if next.norm_one() <= tol * sum.norm_one() {
+                break;
+            }
+        }
+        Ok(sum
+            .scale(T::from_real(
+                <T::Real as NumCast>::from(2f64.powi(k + 1)).unwrap(),
+            ))
+            .into_ndarr())
+    }
+}
+
+#[cfg(test)]
+mod matfun_test {
+    use super::*;
+
+    #[test]
+    fn power() {
+        let a: Ndarr<f64, U2> = Ndarr::from([[2., 1.], [1., 1.]]);
+        assert_eq!(
+            a.matrix_power(0).unwrap(),
+            Ndarr::from([[1., 0.], [0., 1.]])
+        );
+        assert_eq!(a.matrix_power(1).unwrap(), a);
+        assert_eq!(a.matrix_power(3).unwrap(), a.mat_mul(&a).mat_mul(&a));
+        let inv_cube = a.matrix_power(-3).unwrap();
+        assert!(close(
+            &inv_cube.mat_mul(&a.matrix_power(3).unwrap()),
+            &Ndarr::from([[1., 0.], [0., 1.]]),
+            1e-10
+        ));
+        assert!(Ndarr::from([[1., 1.]]).matrix_power(2).is_err());
+    }
+
+    #[test]
+    fn exp_sqrt_log() {
+        //rotation generator, exp gives the rotation by 1 radian
+        let g: Ndarr<f64, U2> = Ndarr::from([[0., -1.], [1., 0.]]);
+        let r = g.expm().unwrap();
+        let (c, s) = (1f64.cos(), 1f64.sin());
+        assert!(close(&r, &Ndarr::from([[c, -s], [s, c]]), 1e-12));
+        let big = Ndarr::from([[-20., 5.], [3., -10.]]);
+        let e = big.expm().unwrap();
+        let e_half = (big.clone() * 0.5).expm().unwrap();
+        assert!(close(&e_half.mat_mul(&e_half), &e, 1e-14));
+        let a: Ndarr<f64, U2> = Ndarr::from([[4., 1., 0.], [1., 3., 1.], [0., 1., 2.]]);
+        let s = a.sqrtm().unwrap();
+        assert!(close(&s.mat_mul(&s), &a, 1e-12));
+        let l = a.logm().unwrap();
+        assert!(close(&l.expm().unwrap(), &a, 1e-10));
+        assert!(close(&a.expm().unwrap().logm().unwrap(), &a, 1e-9));
+        assert!(Ndarr::from([[0., 0.], [0., 1.]]).sqrtm().is_err());
+    }
+
+    #[test]
+    fn sqrt_log_large_determinant() {
+        //det(Y) overflows or underflows, the scaling must not
+        let id: Ndarr<f64, U2> = Ndarr::identity(40);
+        let s = (&id * 1e10).sqrtm().unwrap();
+        assert!(close(&s, &(&id * 1e5), 1e-12));
+        let s = (&id * 1e-10).sqrtm().unwrap();
+        assert!(close(&s, &(&id * 1e-5), 1e-12));
+        let l = (&id * 1e10).logm().unwrap();
+        assert!(close(&l, &(&id * 1e10f64.ln()), 1e-10));
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn complex() {
+        //time evolution exp(-i H t) of a Hermitian Hamiltonian is unitary
+        let h: Ndarr<C<f64>, U2> = Ndarr::from([[C(1., 0.), C(0., -1.)], [C(0., 1.), C(-1., 0.)]]);
+        let u = h.map(|z| *z * C(0., -0.7)).expm().unwrap();
+        let u_h = u.map(|z| z.conj()).t();
+        let id = Ndarr::from([[C(1., 0.), C(0., 0.)], [C(0., 0.), C(1., 0.)]]);
+        assert!(close(&u.mat_mul(&u_h), &id, 1e-12));
+        //logarithm of a matrix with negative eigenvalues only exists in complex arithmetic
+        let m = Ndarr::from([[C(-1., 0.), C(1., 0.)], [C(0., 0.), C(-2., 0.)]]);
+        let m = m.map(|z| *z * C(0., 1.));
+        let l = m.logm().unwrap();
+        assert!(close(&l.expm().unwrap(), &m, 1e-10));
+        let s = m.sqrtm().unwrap();
+        assert!(close(&s.mat_mul(&s), &m, 1e-12));
+    }
+}
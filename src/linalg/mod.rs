@@ -0,0 +1,348 @@
+//!Dense linear algebra on matrices `Ndarr<T, U2>` of real (`f32`, `f64`) or complex (`C<f32>`, `C<f64>`) elements.
+use super::*;
+use num_traits::{NumCast, One, Zero};
+use std::ops::{Div, Mul, Neg};
+
+mod banded;
+mod batched;
+mod cholesky;
+mod eigen;
+mod iterative;
+mod krylov;
+mod lu;
+mod matfun;
+mod norms;
+mod qr;
+mod svd;
+
+pub use banded::{solve_banded, solve_triangular, solve_tridiagonal, BandMatrix};
+#[cfg(feature = "complex")]
+pub use batched::eig;
+pub use batched::{cholesky, det, eigh, inv, slogdet, solve, svd};
+pub use cholesky::{cho_solve, Triangle};
+pub use iterative::{bicgstab, cg, gmres, Convergence, IterOptions, LinearOperator};
+#[cfg(feature = "complex")]
+pub use krylov::arnoldi;
+pub use krylov::{lanczos, Which};
+pub use norms::Norm;
+pub use qr::{lstsq, QrMode};
+
+///Element types supported by the linear algebra routines.
+pub trait LinalgScalar:
+    Copy
+    + Debug
+    + Default
+    + PartialEq
+    + Zero
+    + One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    ///The real type of the modulus, equal to `Self` for real types.
+    type Real: LinalgScalar<Real = Self::Real> + Float;
+    fn from_real(x: Self::Real) -> Self;
+    fn re(self) -> Self::Real;
+    fn im(self) -> Self::Real;
+    fn conj(self) -> Self;
+    fn modulus(self) -> Self::Real;
+    fn principal_sqrt(self) -> Self;
+}
+
+macro_rules! real_scalar {
+    ($t:ty) => {
+        impl LinalgScalar for $t {
+            type Real = $t;
+            fn from_real(x: $t) -> Self {
+                x
+            }
+            fn re(self) -> $t {
+                self
+            }
+            fn im(self) -> $t {
+                0.
+            }
+            fn conj(self) -> Self {
+                self
+            }
+            fn modulus(self) -> $t {
+                self.abs()
+            }
+            fn principal_sqrt(self) -> Self {
+                self.sqrt()
+            }
+        }
+    };
+}
+
+real_scalar!(f32);
+real_scalar!(f64);
+
+#[cfg(feature = "complex")]
+macro_rules! complex_scalar {
+    ($t:ty) => {
+        impl LinalgScalar for C<$t> {
+            type Real = $t;
+            fn from_real(x: $t) -> Self {
+                C(x, 0.)
+            }
+            fn re(self) -> $t {
+                self.0
+            }
+            fn im(self) -> $t {
+                self.1
+            }
+            fn conj(self) -> Self {
+                C(self.0, -self.1)
+            }
+            fn modulus(self) -> $t {
+                self.0.hypot(self.1)
+            }
+            fn principal_sqrt(self) -> Self {
+                self.sqrt()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "complex")]
+complex_scalar!(f32);
+#[cfg(feature = "complex")]
+complex_scalar!(f64);
+
+//Row-major dense matrix used internally by the algorithms.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Mat<T> {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<T>,
+}
+
+impl<T: LinalgScalar> Mat<T> {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Mat {
+            rows,
+            cols,
+            data: vec![T::zero(); rows * cols],
+        }
+    }
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m.data[i * n + i] = T::one();
+        }
+        m
+    }
+    pub fn from_ndarr(a: &Ndarr<T, U2>) -> Self {
+        Mat {
+            rows: a.shape()[0],
+            cols: a.shape()[1],
+            data: a.data.clone(),
+        }
+    }
+    pub fn into_ndarr(self) -> Ndarr<T, U2> {
+        Ndarr {
+            dim: Dim::new(&[self.rows, self.cols]).unwrap(),
+            data: self.data,
+        }
+    }
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self.data[i * self.cols + j]
+    }
+    pub fn set(&mut self, i: usize, j: usize, x: T) {
+        self.data[i * self.cols + j] = x;
+    }
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        for j in 0..self.cols {
+            self.data.swap(a * self.cols + j, b * self.cols + j);
+        }
+    }
+    pub fn swap_cols(&mut self, a: usize, b: usize) {
+        for i in 0..self.rows {
+            self.data.swap(i * self.cols + a, i * self.cols + b);
+        }
+    }
+    //Copy of the rows `r0..r1` and columns `c0..c1`.
+    pub fn block(&self, r0: usize, r1: usize, c0: usize, c1: usize) -> Self {
+        let mut out = Self::zeros(r1 - r0, c1 - c0);
+        for i in r0..r1 {
+            for j in c0..c1 {
+                out.set(i - r0, j - c0, self.get(i, j));
+            }
+        }
+        out
+    }
+    //Conjugate transpose.
+    pub fn adjoint(&self) -> Self {
+        let mut out = Self::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                out.set(j, i, self.get(i, j).conj());
+            }
+        }
+        out
+    }
+    pub fn matmul(&self, other: &Self) -> Self {
+        let mut out = Self::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self.get(i, k);
+                if a == T::zero() {
+                    continue;
+                }
+                for j in 0..other.cols {
+                    let idx = i * other.cols + j;
+                    out.data[idx] = out.data[idx] + a * other.get(k, j);
+                }
+            }
+        }
+        out
+    }
+    //Largest modulus of the elements.
+    pub fn max_modulus(&self) -> T::Real {
+        self.data
+            .iter()
+            .fold(T::Real::zero(), |m, x| m.max(x.modulus()))
+    }
+}
+
+//Checks that the matrix is square and returns its size.
+pub(crate) fn check_square<T: Clone>(a: &Ndarr<T, U2>) -> Result<usize, LinalgError> {
+    let shape = a.shape();
+    if shape[0] != shape[1] {
+        return Err(LinalgError::new(&format!(
+            "Expected a square matrix, found shape {:?}.",
+            shape
+        )));
+    }
+    Ok(shape[0])
+}
+
+//Checks that the matrix is square and equal to its conjugate transpose up to rounding, returns its size.
+pub(crate) fn check_hermitian<T: LinalgScalar>(a: &Mat<T>) -> Result<usize, LinalgError> {
+    if a.rows != a.cols {
+        return Err(LinalgError::new(&format!(
+            "Expected a square matrix, found shape {:?}.",
+            [a.rows, a.cols]
+        )));
+    }
+    let n = a.rows;
+    let tol = a.max_modulus() * T::Real::epsilon() * <T::Real as NumCast>::from(n.max(1)).unwrap();
+    for i in 0..n {
+        for j in 0..i {
+            if (a.get(i, j) - a.get(j, i).conj()).modulus() > tol {
+                return Err(LinalgError::new("Expected a symmetric (Hermitian) matrix."));
+            }
+        }
+    }
+    Ok(n)
+}
+
+//Converts a right-hand side of rank 1 or 2 into a matrix, checking that it has `n` rows.
+pub(crate) fn rhs_to_mat<T: LinalgScalar, R: Unsigned>(
+    b: &Ndarr<T, R>,
+    n: usize,
+) -> Result<Mat<T>, LinalgError> {
+    let shape = b.shape();
+    if shape.is_empty() || shape.len() > 2 || shape[0] != n {
+        return Err(LinalgError::new(&format!(
+            "Right-hand side of shape {:?} is not compatible with a matrix with {} rows.",
+            shape, n
+        )));
+    }
+    Ok(Mat {
+        rows: n,
+        cols: if shape.len() == 2 { shape[1] } else { 1 },
+        data: b.data.clone(),
+    })
+}
+
+//Householder vector `v` and scale `2 / |v|^2` such that `(I - scale v v^H) x` is a multiple of the first unit vector.
+//`x` is reflected onto `-phase(x0) |x| e0` to avoid cancellation. Returns None if `x` is zero.
+pub(crate) fn householder<T: LinalgScalar>(mut x: Vec<T>) -> Option<(Vec<T>, T)> {
+    let norm = x
+        .iter()
+        .fold(T::Real::zero(), |s, e| s + e.modulus().powi(2))
+        .sqrt();
+    if norm == T::Real::zero() {
+        return None;
+    }
+    let x0 = x[0];
+    let phase = if x0.modulus() == T::Real::zero() {
+        T::one()
+    } else {
+        x0 / T::from_real(x0.modulus())
+    };
+    x[0] = x0 + phase * T::from_real(norm);
+    let v_norm2 = x
+        .iter()
+        .fold(T::Real::zero(), |s, e| s + e.modulus().powi(2));
+    let two = T::Real::one() + T::Real::one();
+    Some((x, T::from_real(two / v_norm2)))
+}
+
+//Solves `R X = B` for an upper triangular `R`, using the leading `n x n` block of `R` and the first `n` rows of `B`.
+pub(crate) fn back_substitute<T: LinalgScalar>(r: &Mat<T>, b: &Mat<T>, n: usize) -> Mat<T> {
+    let mut x = Mat::zeros(n, b.cols);
+    for j in 0..b.cols {
+        for i in (0..n).rev() {
+            let mut s = b.get(i, j);
+            for k in i + 1..n {
+                s = s - r.get(i, k) * x.get(k, j);
+            }
+            x.set(i, j, s / r.get(i, i));
+        }
+    }
+    x
+}
+
+//Solves `L X = B` for a lower triangular `L`.
+pub(crate) fn forward_substitute<T: LinalgScalar>(l: &Mat<T>, b: &Mat<T>) -> Mat<T> {
+    let n = l.rows;
+    let mut x = Mat::zeros(n, b.cols);
+    for j in 0..b.cols {
+        for i in 0..n {
+            let mut s = b.get(i, j);
+            for k in 0..i {
+                s = s - l.get(i, k) * x.get(k, j);
+            }
+            x.set(i, j, s / l.get(i, i));
+        }
+    }
+    x
+}
+
+#[cfg(test)]
+pub(crate) use linalg_test::close;
+
+#[cfg(test)]
+mod linalg_test {
+    use super::*;
+
+    //Checks that two matrices are equal up to `tol` in every element.
+    pub(crate) fn close<T: LinalgScalar, R: Unsigned>(
+        a: &Ndarr<T, R>,
+        b: &Ndarr<T, R>,
+        tol: T::Real,
+    ) -> bool {
+        a.shape() == b.shape()
+            && a.data
+                .iter()
+                .zip(&b.data)
+                .all(|(x, y)| (*x - *y).modulus() <= tol)
+    }
+
+    #[test]
+    fn mat_helpers() {
+        let a = Ndarr::from([[1., 2.], [3., 4.]]);
+        let m = Mat::from_ndarr(&a);
+        assert_eq!(m.clone().into_ndarr(), a);
+        assert_eq!(m.matmul(&Mat::identity(2)), m);
+        assert_eq!(m.adjoint().into_ndarr(), a.t());
+        assert_eq!(m.block(1, 2, 0, 2).into_ndarr(), Ndarr::from([[3., 4.]]));
+        assert_eq!(m.max_modulus(), 4.);
+        assert!(close(&a, &(a.clone() + 1e-12), 1e-10));
+    }
+}
@@ -0,0 +1,178 @@
+use super::svd::svd_factor;
+use super::*;
+
+///Order of a vector or matrix norm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Norm<F> {
+    ///Sum of the moduli for vectors, maximum absolute column sum for matrices.
+    One,
+    ///Euclidean norm for vectors, largest singular value for matrices.
+    Two,
+    ///Largest modulus for vectors, maximum absolute row sum for matrices.
+    Inf,
+    ///`(sum |x|^p)^(1/p)` for vectors, with `p > 0`.
+    P(F),
+    ///Frobenius norm, the Euclidean norm of all the elements.
+    Fro,
+    ///Nuclear norm of a matrix, the sum of its singular values.
+    Nuc,
+}
+
+//Vector norm of the moduli of the elements.
+fn vector_norm<F: Float + Debug>(moduli: &[F], ord: Norm<F>) -> Result<F, LinalgError> {
+    let sum = |f: &dyn Fn(F) -> F| moduli.iter().fold(F::zero(), |s, x| s + f(*x));
+    match ord {
+        Norm::One => Ok(sum(&|x| x)),
+        Norm::Two | Norm::Fro => Ok(sum(&|x| x * x).sqrt()),
+        Norm::Inf => Ok(moduli.iter().fold(F::zero(), |m, x| m.max(*x))),
+        Norm::P(p) if p > F::zero() => Ok(sum(&|x| x.powf(p)).powf(p.recip())),
+        _ => Err(LinalgError::new(&format!(
+            "Invalid norm order {:?} for vectors.",
+            ord
+        ))),
+    }
+}
+
+impl<T: LinalgScalar, R: Unsigned> Ndarr<T, R> {
+    ///Norm of a vector or a matrix. Vectors support every order but `Norm::Nuc`, matrices every order but `Norm::P`,
+    ///arrays of higher rank only the Frobenius norm.
+    ///```
+    ///use rapl::*;
+    ///use rapl::linalg::Norm;
+    ///let v = Ndarr::from([3., -4.]);
+    ///assert_eq!(v.norm(Norm::Two).unwrap(), 5.);
+    ///assert_eq!(v.norm(Norm::One).unwrap(), 7.);
+    ///let a = Ndarr::from([[1., -2.], [3., 4.]]);
+    ///assert_eq!(a.norm(Norm::One).unwrap(), 6.);
+    ///assert_eq!(a.norm(Norm::Inf).unwrap(), 7.);
+    ///```
+    pub fn norm(&self, ord: Norm<T::Real>) -> Result<T::Real, LinalgError> {
+        let moduli: Vec<T::Real> = self.data.iter().map(|x| x.modulus()).collect();
+        match (self.rank(), ord) {
+            (_, Norm::Fro) => vector_norm(&moduli, ord),
+            (1, _) => vector_norm(&moduli, ord),
+            (2, Norm::One) | (2, Norm::Inf) => {
+                let (m, n) = (self.shape()[0], self.shape()[1]);
+                let (outer, inner, stride) = match ord {
+                    Norm::One => (n, m, (n, 1)),
+                    _ => (m, n, (1, n)),
+                };
+                Ok((0..outer)
+                    .map(|o| {
+                        (0..inner).fold(T::Real::zero(), |s, i| {
+                            s + moduli[i * stride.0 + o * stride.1]
+                        })
+                    })
+                    .fold(T::Real::zero(), |m, x| m.max(x)))
+            }
+            (2, Norm::Two) | (2, Norm::Nuc) => {
+                let s = svd_factor(&Mat {
+                    rows: self.shape()[0],
+                    cols: self.shape()[1],
+                    data: self.data.clone(),
+                })?
+                .s;
+                Ok(match ord {
+                    Norm::Two => s.first().copied().unwrap_or(T::Real::zero()),
+                    _ => s.iter().fold(T::Real::zero(), |a, x| a + *x),
+                })
+            }
+            _ => Err(LinalgError::new(&format!(
+                "Invalid norm order {:?} for an array of rank {}.",
+                ord,
+                self.rank()
+            ))),
+        }
+    }
+
+    ///Vector norm of every lane along an axis. Matrix orders other than `Norm::Fro` are not supported.
+    ///```
+    ///use rapl::*;
+    ///use rapl::linalg::Norm;
+    ///let a = Ndarr::from([[3., 4.], [6., 8.]]);
+    ///assert_eq!(a.norm_axis(Norm::Two, 1).unwrap(), Ndarr::from([5., 10.]));
+    ///```
+    pub fn norm_axis(
+        &self,
+        ord: Norm<T::Real>,
+        axis: usize,
+    ) -> Result<Ndarr<T::Real, Sub1<R>>, LinalgError>
+    where
+        R: Sub<B1>,
+        <R as Sub<B1>>::Output: Unsigned,
+    {
+        if axis >= self.rank() {
+            return Err(LinalgError::new("Axis grater than rank"));
+        }
+        let mut result = Ok(());
+        let lanes = self.map_lanes(axis, 1, |lane| {
+            let moduli: Vec<T::Real> = lane.iter().map(|x| x.modulus()).collect();
+            match vector_norm(&moduli, ord) {
+                Ok(x) => vec![x],
+                Err(e) => {
+                    result = Err(e);
+                    vec![T::Real::zero()]
+                }
+            }
+        })?;
+        result?;
+        let mut shape = self.shape().to_vec();
+        shape.remove(axis);
+        Ok(Ndarr {
+            data: lanes.data,
+            dim: Dim::new(&shape)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod norms_test {
+    use super::*;
+
+    #[test]
+    fn vector() {
+        let v: Ndarr<f64, U1> = Ndarr::from([1., -2., 2.]);
+        assert_eq!(v.norm(Norm::One).unwrap(), 5.);
+        assert_eq!(v.norm(Norm::Two).unwrap(), 3.);
+        assert_eq!(v.norm(Norm::Fro).unwrap(), 3.);
+        assert_eq!(v.norm(Norm::Inf).unwrap(), 2.);
+        assert!((v.norm(Norm::P(3.)).unwrap() - 17f64.cbrt()).abs() < 1e-12);
+        assert!(v.norm(Norm::P(0.)).is_err());
+        assert!(v.norm(Norm::Nuc).is_err());
+    }
+
+    #[test]
+    fn matrix() {
+        let a: Ndarr<f64, U2> = Ndarr::from([[1., -2., 0.], [3., 4., -1.]]);
+        assert_eq!(a.norm(Norm::One).unwrap(), 6.);
+        assert_eq!(a.norm(Norm::Inf).unwrap(), 8.);
+        assert!((a.norm(Norm::Fro).unwrap() - 31f64.sqrt()).abs() < 1e-12);
+        let d: Ndarr<f64, U2> = Ndarr::from([[3., 0.], [0., -4.]]);
+        assert!((d.norm(Norm::Two).unwrap() - 4.).abs() < 1e-12);
+        assert!((d.norm(Norm::Nuc).unwrap() - 7.).abs() < 1e-12);
+        assert!(a.norm(Norm::P(2.)).is_err());
+        let t: Ndarr<f64, U3> = Ndarr::from([[[1., 1.], [1., 1.]], [[1., 1.], [1., 1.]]]);
+        assert!((t.norm(Norm::Fro).unwrap() - 8f64.sqrt()).abs() < 1e-12);
+        assert!(t.norm(Norm::One).is_err());
+    }
+
+    #[test]
+    fn along_axis() {
+        let a: Ndarr<f64, U2> = Ndarr::from([[1., -2., 0.], [3., 4., -1.]]);
+        assert_eq!(
+            a.norm_axis(Norm::One, 0).unwrap(),
+            Ndarr::from([4., 6., 1.])
+        );
+        assert_eq!(a.norm_axis(Norm::Inf, 1).unwrap(), Ndarr::from([2., 4.]));
+        assert!(a.norm_axis(Norm::Nuc, 1).is_err());
+        assert!(a.norm_axis(Norm::One, 2).is_err());
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn complex() {
+        let v: Ndarr<C<f64>, U1> = Ndarr::from([C(3., 4.), C(0., 0.)]);
+        assert_eq!(v.norm(Norm::Two).unwrap(), 5.);
+        assert_eq!(v.norm(Norm::Inf).unwrap(), 5.);
+    }
+}
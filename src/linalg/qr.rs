@@ -0,0 +1,277 @@
+use super::*;
+
+///Shape of the factors returned by `qr`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrMode {
+    ///For a `m x n` matrix, `Q` is `m x k` and `R` is `k x n` with `k = min(m, n)`.
+    Reduced,
+    ///`Q` is the full `m x m` unitary matrix and `R` is `m x n`.
+    Complete,
+}
+
+type LstsqResult<T, R> =
+    Result<(Ndarr<T, R>, Ndarr<<T as LinalgScalar>::Real, U1>, usize), LinalgError>;
+
+//Householder QR, `A P = Q R` with `Q` the complete `m x m` factor and `R` of shape `m x n`.
+//With `pivoting` the column with the largest remaining norm is moved forward at every step,
+//so the modulus of the diagonal of `R` is non increasing. `perm[j]` is the column of `A` at position `j`.
+pub(crate) fn householder_qr<T: LinalgScalar>(
+    a: &Mat<T>,
+    pivoting: bool,
+) -> (Mat<T>, Mat<T>, Vec<usize>) {
+    let (m, n) = (a.rows, a.cols);
+    let mut r = a.clone();
+    let mut q = Mat::identity(m);
+    let mut perm: Vec<usize> = (0..n).collect();
+    for k in 0..m.min(n) {
+        if pivoting {
+            let col_norm =
+                |j: usize| (k..m).fold(T::Real::zero(), |s, i| s + r.get(i, j).modulus().powi(2));
+            let p = (k..n)
+                .max_by(|i, j| {
+                    col_norm(*i)
+                        .partial_cmp(&col_norm(*j))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap();
+            if p != k {
+                r.swap_cols(p, k);
+                perm.swap(p, k);
+            }
+        }
+        let (v, scale) = match householder((k..m).map(|i| r.get(i, k)).collect()) {
+            Some(h) => h,
+            None => continue,
+        };
+        //R <- H R, with H = I - 2 v v^H / |v|^2
+        for j in k..n {
+            let dot = v
+                .iter()
+                .enumerate()
+                .fold(T::zero(), |s, (i, vi)| s + vi.conj() * r.get(k + i, j));
+            for (i, vi) in v.iter().enumerate() {
+                r.set(k + i, j, r.get(k + i, j) - scale * *vi * dot);
+            }
+        }
+        for i in k + 1..m {
+            r.set(i, k, T::zero());
+        }
+        //Q <- Q H
+        for i in 0..m {
+            let dot = v
+                .iter()
+                .enumerate()
+                .fold(T::zero(), |s, (l, vl)| s + q.get(i, k + l) * *vl);
+            for (l, vl) in v.iter().enumerate() {
+                q.set(i, k + l, q.get(i, k + l) - scale * dot * vl.conj());
+            }
+        }
+    }
+    (q, r, perm)
+}
+
+//Trims the factors of a complete QR to the reduced form.
+fn reduce<T: LinalgScalar>(q: Mat<T>, r: Mat<T>, mode: QrMode) -> (Mat<T>, Mat<T>) {
+    let (m, n) = (r.rows, r.cols);
+    let k = m.min(n);
+    match mode {
+        QrMode::Complete => (q, r),
+        QrMode::Reduced => (q.block(0, m, 0, k), r.block(0, k, 0, n)),
+    }
+}
+
+impl<T: LinalgScalar> Ndarr<T, U2> {
+    ///Householder QR decomposition `A = Q R`, where `Q` has orthonormal columns and `R` is upper triangular.
+    ///```
+    ///use rapl::*;
+    ///use rapl::linalg::QrMode;
+    ///let a = Ndarr::from([[3., 1.], [4., 2.], [0., 2.]]);
+    ///let (q, r) = a.qr(QrMode::Reduced);
+    ///assert_eq!(q.shape(), &[3, 2]);
+    ///assert_eq!(r.shape(), &[2, 2]);
+    ///let (q, r) = a.qr(QrMode::Complete);
+    ///assert_eq!(q.shape(), &[3, 3]);
+    ///assert!((q.mat_mul(&r) - a).abs().data.iter().all(|x: &f64| *x < 1e-12));
+    ///```
+    pub fn qr(&self, mode: QrMode) -> (Self, Self) {
+        let (q, r, _) = householder_qr(&Mat::from_ndarr(self), false);
+        let (q, r) = reduce(q, r, mode);
+        (q.into_ndarr(), r.into_ndarr())
+    }
+
+    ///QR decomposition with column pivoting `A[:, p] = Q R`, in reduced form. The modulus of the diagonal of `R`
+    ///is non increasing, which reveals the numerical rank of the matrix. Returns `(Q, R, p)`.
+    pub fn qr_pivoted(&self) -> (Self, Self, Ndarr<usize, U1>) {
+        let (q, r, perm) = householder_qr(&Mat::from_ndarr(self), true);
+        let (q, r) = reduce(q, r, QrMode::Reduced);
+        (q.into_ndarr(), r.into_ndarr(), Ndarr::from(perm))
+    }
+}
+
+///Least-squares solution of `A x = b`, minimizing `|b - A x|` with column pivoted QR.
+///The right-hand side `b` can be a vector or a matrix, in which case every column is solved independently.
+///Returns the solution, the squared residual norm of each column and the numerical rank of `A`.
+///For rank deficient matrices the solution has at most `rank` non zero entries.
+///```
+///use rapl::*;
+///let a = Ndarr::from([[1., 0.], [1., 1.], [1., 2.]]);
+///let b = Ndarr::from([1., 2., 3.]);
+///let (x, residuals, rank) = linalg::lstsq(&a, &b).unwrap();
+///assert!(x.data.iter().zip([1f64, 1.]).all(|(a, b)| (a - b).abs() < 1e-12));
+///assert!(residuals.data[0] < 1e-12);
+///assert_eq!(rank, 2);
+///```
+pub fn lstsq<T: LinalgScalar, R: Unsigned>(a: &Ndarr<T, U2>, b: &Ndarr<T, R>) -> LstsqResult<T, R> {
+    let (m, n) = (a.shape()[0], a.shape()[1]);
+    let rhs = rhs_to_mat(b, m)?;
+    let mat = Mat::from_ndarr(a);
+    let (q, r, perm) = householder_qr(&mat, true);
+    let tol = <T::Real as NumCast>::from(m.max(n)).unwrap()
+        * T::Real::epsilon()
+        * r.data.first().map_or(T::Real::zero(), |x| x.modulus());
+    let rank = (0..m.min(n))
+        .take_while(|i| r.get(*i, *i).modulus() > tol)
+        .count();
+    let z = back_substitute(&r, &q.adjoint().matmul(&rhs), rank);
+    let mut x = Mat::zeros(n, rhs.cols);
+    for (i, p) in perm.iter().take(rank).enumerate() {
+        for j in 0..rhs.cols {
+            x.set(*p, j, z.get(i, j));
+        }
+    }
+    let fitted = mat.matmul(&x);
+    let residuals: Vec<T::Real> = (0..rhs.cols)
+        .map(|j| {
+            (0..m).fold(T::Real::zero(), |s, i| {
+                s + (rhs.get(i, j) - fitted.get(i, j)).modulus().powi(2)
+            })
+        })
+        .collect();
+    let mut shape = b.shape().to_vec();
+    shape[0] = n;
+    Ok((
+        Ndarr {
+            data: x.data,
+            dim: Dim::new(&shape)?,
+        },
+        Ndarr {
+            dim: Dim::new(&[residuals.len()])?,
+            data: residuals,
+        },
+        rank,
+    ))
+}
+
+#[cfg(test)]
+mod qr_test {
+    use super::*;
+
+    //Checks `Q^H Q = I` and that `R` is upper triangular.
+    fn check_factors<T: LinalgScalar>(q: &Mat<T>, r: &Mat<T>) {
+        let k = q.cols;
+        let gram = q.adjoint().matmul(q);
+        assert!(close(
+            &gram.into_ndarr(),
+            &Mat::identity(k).into_ndarr(),
+            T::Real::epsilon() * <T::Real as NumCast>::from(100).unwrap()
+        ));
+        for i in 0..r.rows {
+            for j in 0..i.min(r.cols) {
+                assert_eq!(r.get(i, j), T::zero());
+            }
+        }
+    }
+
+    #[test]
+    fn qr_modes() {
+        let a: Ndarr<f64, U2> =
+            Ndarr::from([[2., -1., 3.], [4., 0., 1.], [-2., 5., 7.], [1., 1., 1.]]);
+        for mode in [QrMode::Reduced, QrMode::Complete] {
+            let (q, r) = a.qr(mode);
+            check_factors(&Mat::from_ndarr(&q), &Mat::from_ndarr(&r));
+            assert!(close(&q.mat_mul(&r), &a, 1e-12));
+        }
+        assert_eq!(a.qr(QrMode::Reduced).0.shape(), &[4, 3]);
+        assert_eq!(a.qr(QrMode::Complete).1.shape(), &[4, 3]);
+        //wide matrix
+        let w = a.t();
+        let (q, r) = w.qr(QrMode::Reduced);
+        assert_eq!((q.shape(), r.shape()), (&[3, 3][..], &[3, 4][..]));
+        assert!(close(&q.mat_mul(&r), &w, 1e-12));
+    }
+
+    #[test]
+    fn pivoted() {
+        //the third column is the sum of the first two
+        let a: Ndarr<f64, U2> =
+            Ndarr::from([[1., 2., 3.], [4., 5., 9.], [7., 8., 15.], [1., 0., 1.]]);
+        let (q, r, p) = a.qr_pivoted();
+        check_factors(&Mat::from_ndarr(&q), &Mat::from_ndarr(&r));
+        let permuted = Ndarr::from(
+            (0..4)
+                .flat_map(|i| {
+                    p.data
+                        .iter()
+                        .map(|j| a.data[i * 3 + j])
+                        .collect::<Vec<f64>>()
+                })
+                .collect::<Vec<f64>>(),
+        )
+        .reshape([4, 3])
+        .unwrap();
+        assert!(close(&q.mat_mul(&r), &permuted, 1e-12));
+        let diag: Vec<f64> = (0..3).map(|i| r.data[i * 3 + i].abs()).collect();
+        assert!(diag[0] >= diag[1] && diag[1] >= diag[2]);
+        assert!(diag[2] < 1e-12);
+    }
+
+    #[test]
+    fn least_squares() {
+        //fit y = 1 + 2 t to noisy data, compared with the normal equations
+        let a = Ndarr::from([[1., 0.], [1., 1.], [1., 2.], [1., 3.]]);
+        let b = Ndarr::from([1.1, 2.9, 5.2, 6.8]);
+        let (x, res, rank) = lstsq(&a, &b).unwrap();
+        let normal = solve(&a.t().mat_mul(&a), &a.t().mat_mul(&b)).unwrap();
+        assert!(close(&x, &normal, 1e-12));
+        assert_eq!(rank, 2);
+        let r = b - a.mat_mul(&x);
+        assert!((res.data[0] - r.data.iter().map(|e| e * e).sum::<f64>()).abs() < 1e-12);
+        //rank deficient system with several right-hand sides
+        let d = Ndarr::from([[1., 2.], [2., 4.], [3., 6.]]);
+        let bs = Ndarr::from([[1., 0.], [2., 1.], [3., 0.]]);
+        let (x, res, rank) = lstsq(&d, &bs).unwrap();
+        assert_eq!(rank, 1);
+        assert_eq!(x.shape(), &[2, 2]);
+        assert!(res.data[0] < 1e-12 && res.data[1] > 0.);
+        assert!(lstsq(&d, &Ndarr::from([1., 2.])).is_err());
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn complex() {
+        let a: Ndarr<C<f64>, U2> = Ndarr::from([
+            [C(1., 1.), C(2., 0.)],
+            [C(0., -1.), C(3., 2.)],
+            [C(1., 0.), C(0., 1.)],
+        ]);
+        let (q, r) = a.qr(QrMode::Complete);
+        check_factors(&Mat::from_ndarr(&q), &Mat::from_ndarr(&r));
+        assert!(close(&q.mat_mul(&r), &a, 1e-12));
+        let b = Ndarr::from([C(1., 0.), C(0., 1.), C(2., -1.)]);
+        let (x, res, rank) = lstsq(&a, &b).unwrap();
+        assert_eq!(rank, 2);
+        //the residual is orthogonal to the columns of A
+        let r = b - a.mat_mul(&x);
+        let a_h = Ndarr::from(
+            [0, 1]
+                .iter()
+                .flat_map(|j| (0..3).map(move |i| (i, *j)))
+                .map(|(i, j)| a.data[i * 2 + j].conj())
+                .collect::<Vec<C<f64>>>(),
+        )
+        .reshape([2, 3])
+        .unwrap();
+        assert!(a_h.mat_mul(&r).data.iter().all(|z| z.modulus() < 1e-12));
+        assert!(res.data[0] > 0.);
+    }
+}
@@ -0,0 +1,355 @@
+use super::eigen::{jacobi_rotation, MAX_SWEEPS};
+use super::*;
+use std::cmp::Ordering;
+
+type SvdResult<T> = Result<
+    (
+        Ndarr<T, U2>,
+        Ndarr<<T as LinalgScalar>::Real, U1>,
+        Ndarr<T, U2>,
+    ),
+    LinalgError,
+>;
+
+//Thin singular value decomposition `A = U S V^H` with the singular values in descending order.
+//The columns of `U` for numerically zero singular values are zero, `V` is always unitary.
+pub(crate) struct SvdFactor<T: LinalgScalar> {
+    pub u: Mat<T>,
+    pub s: Vec<T::Real>,
+    pub v: Mat<T>,
+}
+
+//One-sided Jacobi SVD of a matrix with at least as many rows as columns. The columns of `A` are rotated until they are
+//mutually orthogonal, the rotations accumulate in `V` and the column norms are the singular values.
+fn one_sided_jacobi<T: LinalgScalar>(a: &Mat<T>) -> Result<SvdFactor<T>, LinalgError> {
+    let (m, n) = (a.rows, a.cols);
+    let mut u = a.clone();
+    let mut v: Mat<T> = Mat::identity(n);
+    let eps = T::Real::epsilon();
+    //columns below this squared norm are numerically zero and only carry rounding noise
+    let negligible = eps
+        * eps
+        * a.data
+            .iter()
+            .fold(T::Real::zero(), |s, x| s + x.modulus().powi(2));
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let (mut alpha, mut beta, mut gamma) =
+                    (T::Real::zero(), T::Real::zero(), T::zero());
+                for i in 0..m {
+                    let (x, y) = (u.get(i, p), u.get(i, q));
+                    alpha = alpha + x.modulus().powi(2);
+                    beta = beta + y.modulus().powi(2);
+                    gamma = gamma + x.conj() * y;
+                }
+                if alpha <= negligible
+                    || beta <= negligible
+                    || gamma.modulus() <= eps * (alpha * beta).sqrt()
+                {
+                    continue;
+                }
+                rotated = true;
+                let [jpp, jpq, jqp, jqq] = jacobi_rotation(alpha, beta, gamma);
+                for i in 0..m {
+                    let (x, y) = (u.get(i, p), u.get(i, q));
+                    u.set(i, p, x * jpp + y * jqp);
+                    u.set(i, q, x * jpq + y * jqq);
+                }
+                for i in 0..n {
+                    let (x, y) = (v.get(i, p), v.get(i, q));
+                    v.set(i, p, x * jpp + y * jqp);
+                    v.set(i, q, x * jpq + y * jqq);
+                }
+            }
+        }
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(LinalgError::new(
+            "The Jacobi SVD iteration did not converge.",
+        ));
+    }
+    let norms: Vec<T::Real> = (0..n)
+        .map(|j| {
+            (0..m)
+                .fold(T::Real::zero(), |s, i| s + u.get(i, j).modulus().powi(2))
+                .sqrt()
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|i, j| norms[*j].partial_cmp(&norms[*i]).unwrap_or(Ordering::Equal));
+    let mut factor = SvdFactor {
+        u: Mat::zeros(m, n),
+        s: order.iter().map(|j| norms[*j]).collect(),
+        v: Mat::zeros(n, n),
+    };
+    //the directions of numerically zero columns are rounding noise, they are left as zero
+    let cutoff = default_tol::<T>(&factor.s, m, n);
+    for (new, old) in order.iter().enumerate() {
+        let s = norms[*old];
+        for i in 0..m {
+            if s > cutoff {
+                factor.u.set(i, new, u.get(i, *old) / T::from_real(s));
+            }
+        }
+        for i in 0..n {
+            factor.v.set(i, new, v.get(i, *old));
+        }
+    }
+    Ok(factor)
+}
+
+//Thin SVD of any matrix, transposing wide matrices so that the Jacobi iteration runs on the tall one.
+pub(crate) fn svd_factor<T: LinalgScalar>(a: &Mat<T>) -> Result<SvdFactor<T>, LinalgError> {
+    if a.rows >= a.cols {
+        one_sided_jacobi(a)
+    } else {
+        let f = one_sided_jacobi(&a.adjoint())?;
+        Ok(SvdFactor {
+            u: f.v,
+            s: f.s,
+            v: f.u,
+        })
+    }
+}
+
+//Replaces the zero columns of `u`, whose other columns are orthonormal, and appends new columns up to `cols`,
+//so that all the columns are orthonormal. Every new column is the unit vector with the largest component
+//orthogonal to the current columns.
+fn complete_basis<T: LinalgScalar>(u: &Mat<T>, cols: usize) -> Mat<T> {
+    let m = u.rows;
+    let half = T::Real::one() / (T::Real::one() + T::Real::one());
+    let column = |j: usize| (0..m).map(|i| u.get(i, j)).collect::<Vec<T>>();
+    let norm = |x: &[T]| {
+        x.iter()
+            .fold(T::Real::zero(), |s, e| s + e.modulus().powi(2))
+            .sqrt()
+    };
+    let valid: Vec<bool> = (0..cols)
+        .map(|j| j < u.cols && norm(&column(j)) > half)
+        .collect();
+    let mut basis: Vec<Vec<T>> = (0..cols).filter(|j| valid[*j]).map(column).collect();
+    let orthogonalize = |basis: &[Vec<T>], mut x: Vec<T>| {
+        for _ in 0..2 {
+            for b in basis {
+                let dot = b
+                    .iter()
+                    .zip(&x)
+                    .fold(T::zero(), |s, (bi, xi)| s + bi.conj() * *xi);
+                x.iter_mut()
+                    .zip(b)
+                    .for_each(|(xi, bi)| *xi = *xi - dot * *bi);
+            }
+        }
+        x
+    };
+    let mut out = Mat::zeros(m, cols);
+    for (j, is_valid) in valid.iter().enumerate() {
+        let x = if *is_valid {
+            column(j)
+        } else {
+            let x = (0..m)
+                .map(|e| {
+                    let unit = (0..m)
+                        .map(|i| if i == e { T::one() } else { T::zero() })
+                        .collect();
+                    orthogonalize(&basis, unit)
+                })
+                .max_by(|x, y| norm(x).partial_cmp(&norm(y)).unwrap_or(Ordering::Equal))
+                .unwrap();
+            let r = norm(&x);
+            let x: Vec<T> = x.into_iter().map(|e| e / T::from_real(r)).collect();
+            basis.push(x.clone());
+            x
+        };
+        for (i, e) in x.into_iter().enumerate() {
+            out.set(i, j, e);
+        }
+    }
+    out
+}
+
+//Default tolerance for the numerical rank, `max(m, n) * eps * s_max`.
+fn default_tol<T: LinalgScalar>(s: &[T::Real], m: usize, n: usize) -> T::Real {
+    <T::Real as NumCast>::from(m.max(n)).unwrap()
+        * T::Real::epsilon()
+        * s.first().copied().unwrap_or(T::Real::zero())
+}
+
+impl<T: LinalgScalar> Ndarr<T, U2> {
+    ///Singular value decomposition `A = U S V^H` computed with the one-sided Jacobi algorithm. Returns `(U, S, V^H)` with
+    ///the singular values `S` in descending order. For a `m x n` matrix and `k = min(m, n)`, `U` is `m x k` and `V^H` is
+    ///`k x n`, or with `full_matrices` the unitary `m x m` and `n x n` matrices.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[3f64, 0.], [4., 5.]]);
+    ///let (u, s, vh) = a.svd(false).unwrap();
+    ///assert_eq!((u.shape(), vh.shape()), (&[2, 2][..], &[2, 2][..]));
+    ///assert!((s.data[0] - 45f64.sqrt()).abs() < 1e-12);
+    ///assert!((s.data[1] - 5f64.sqrt()).abs() < 1e-12);
+    ///```
+    pub fn svd(&self, full_matrices: bool) -> SvdResult<T> {
+        let (m, n) = (self.shape()[0], self.shape()[1]);
+        let f = svd_factor(&Mat::from_ndarr(self))?;
+        let k = f.s.len();
+        let (u_cols, v_cols) = if full_matrices { (m, n) } else { (k, k) };
+        Ok((
+            complete_basis(&f.u, u_cols).into_ndarr(),
+            Ndarr {
+                dim: Dim::new(&[k])?,
+                data: f.s,
+            },
+            complete_basis(&f.v, v_cols).adjoint().into_ndarr(),
+        ))
+    }
+
+    ///Moore-Penrose pseudo-inverse, computed from the SVD ignoring the singular values smaller than `rcond` times the largest one.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1., 2.], [2., 4.]]);
+    ///let p = a.pinv(1e-12).unwrap();
+    ///assert!((a.mat_mul(&p).mat_mul(&a) - a).abs().data.iter().all(|x: &f64| *x < 1e-12));
+    ///```
+    pub fn pinv(&self, rcond: T::Real) -> Result<Self, LinalgError> {
+        let f = svd_factor(&Mat::from_ndarr(self))?;
+        let cutoff = rcond * f.s.first().copied().unwrap_or(T::Real::zero());
+        let mut v = f.v;
+        for (j, s) in f.s.iter().enumerate() {
+            let inv = if *s > cutoff {
+                T::from_real(s.recip())
+            } else {
+                T::zero()
+            };
+            for i in 0..v.rows {
+                v.set(i, j, v.get(i, j) * inv);
+            }
+        }
+        Ok(v.matmul(&f.u.adjoint()).into_ndarr())
+    }
+
+    ///Numerical rank, the number of singular values greater than `tol`. With None the tolerance is `max(m, n) * eps * s_max`.
+    pub fn matrix_rank(&self, tol: Option<T::Real>) -> Result<usize, LinalgError> {
+        let (m, n) = (self.shape()[0], self.shape()[1]);
+        let s = svd_factor(&Mat::from_ndarr(self))?.s;
+        let tol = tol.unwrap_or_else(|| default_tol::<T>(&s, m, n));
+        Ok(s.iter().filter(|x| **x > tol).count())
+    }
+
+    ///Condition number in the 2-norm, the ratio between the largest and the smallest singular values.
+    ///It is infinite for singular matrices.
+    pub fn cond(&self) -> Result<T::Real, LinalgError> {
+        let s = svd_factor(&Mat::from_ndarr(self))?.s;
+        match (s.first(), s.last()) {
+            (Some(max), Some(min)) => Ok(*max / *min),
+            _ => Err(LinalgError::new(
+                "Can not compute the condition number of an empty matrix.",
+            )),
+        }
+    }
+
+    ///Orthonormal basis of the null space, as the columns of a `n x (n - rank)` matrix.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1., 1.], [1., 1.]]);
+    ///let z = a.null_space().unwrap();
+    ///assert_eq!(z.shape(), &[2, 1]);
+    ///assert!(a.mat_mul(&z).data.iter().all(|x: &f64| x.abs() < 1e-12));
+    ///```
+    pub fn null_space(&self) -> Result<Self, LinalgError> {
+        let (m, n) = (self.shape()[0], self.shape()[1]);
+        let f = svd_factor(&Mat::from_ndarr(self))?;
+        let tol = default_tol::<T>(&f.s, m, n);
+        let rank = f.s.iter().filter(|x| **x > tol).count();
+        Ok(complete_basis(&f.v, n).block(0, n, rank, n).into_ndarr())
+    }
+}
+
+#[cfg(test)]
+mod svd_test {
+    use super::*;
+
+    //Checks `A = U S V^H` and the orthonormality of the columns of `U` and the rows of `V^H`.
+    fn check<T: LinalgScalar>(a: &Ndarr<T, U2>, full: bool) {
+        let (u, s, vh) = a.svd(full).unwrap();
+        let (u, vh) = (Mat::from_ndarr(&u), Mat::from_ndarr(&vh));
+        let tol = <T::Real as NumCast>::from(1e-10).unwrap();
+        let mut us = u.clone();
+        for j in 0..s.data.len() {
+            for i in 0..us.rows {
+                us.set(i, j, us.get(i, j) * T::from_real(s.data[j]));
+            }
+        }
+        let k = s.data.len();
+        let rebuilt = us
+            .block(0, u.rows, 0, k)
+            .matmul(&vh.block(0, k, 0, vh.cols));
+        assert!(close(&rebuilt.into_ndarr(), a, tol));
+        let uu = u.adjoint().matmul(&u).into_ndarr();
+        assert!(close(&uu, &Mat::identity(u.cols).into_ndarr(), tol));
+        let vv = vh.matmul(&vh.adjoint()).into_ndarr();
+        assert!(close(&vv, &Mat::identity(vh.rows).into_ndarr(), tol));
+        assert!(s.data.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn decomposition() {
+        let tall: Ndarr<f64, U2> =
+            Ndarr::from([[1., 2., 3.], [4., 5., 6.], [7., 8., 10.], [1., 0., 1.]]);
+        let rank_deficient: Ndarr<f64, U2> =
+            Ndarr::from([[1., 2., 3.], [2., 4., 6.], [1., 1., 1.]]);
+        for a in [tall.clone(), tall.t(), rank_deficient] {
+            check(&a, false);
+            check(&a, true);
+        }
+        let (u, s, vh) = tall.t().svd(true).unwrap();
+        assert_eq!(
+            (u.shape(), s.shape(), vh.shape()),
+            (&[3, 3][..], &[3][..], &[4, 4][..])
+        );
+        let (_, s, _) = Ndarr::from([[0., 2.], [3., 0.]]).svd(false).unwrap();
+        assert!(close(&s, &Ndarr::from([3., 2.]), 1e-12));
+    }
+
+    #[test]
+    fn rank_pinv_cond() {
+        let a: Ndarr<f64, U2> = Ndarr::from([[1., 2., 3.], [2., 4., 6.], [1., 1., 1.]]);
+        assert_eq!(a.matrix_rank(None).unwrap(), 2);
+        assert_eq!(a.matrix_rank(Some(1e3)).unwrap(), 0);
+        let p = a.pinv(1e-12).unwrap();
+        assert!(close(&a.mat_mul(&p).mat_mul(&a), &a, 1e-10));
+        assert!(close(&p.mat_mul(&a).mat_mul(&p), &p, 1e-10));
+        let b = Ndarr::from([[2., 1.], [1., 3.]]);
+        assert!(close(&b.pinv(1e-12).unwrap(), &inv(&b).unwrap(), 1e-12));
+        let d: Ndarr<f64, U2> = Ndarr::from([[1., 0.], [0., 10.]]);
+        assert!((d.cond().unwrap() - 10.).abs() < 1e-12);
+        assert!(Ndarr::from([[1f64, 1.], [1., 1.]])
+            .cond()
+            .unwrap()
+            .is_infinite());
+        let z = a.null_space().unwrap();
+        assert_eq!(z.shape(), &[3, 1]);
+        assert!(a.mat_mul(&z).data.iter().all(|x| x.abs() < 1e-10));
+        assert_eq!(b.null_space().unwrap().shape(), &[2, 0]);
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn complex() {
+        let a: Ndarr<C<f64>, U2> = Ndarr::from([
+            [C(1., 1.), C(2., 0.), C(0., 1.)],
+            [C(0., -1.), C(3., 2.), C(1., 0.)],
+        ]);
+        check(&a, false);
+        check(&a, true);
+        let p = a.pinv(1e-12).unwrap();
+        let id = Ndarr::from([[C(1., 0.), C(0., 0.)], [C(0., 0.), C(1., 0.)]]);
+        assert!(close(&a.mat_mul(&p), &id, 1e-10));
+        assert_eq!(a.matrix_rank(None).unwrap(), 2);
+    }
+}
@@ -2,7 +2,32 @@ use super::*;
 use std::ops::*;
 use typenum::{Maximum, Unsigned};
 
+//Operands are promoted to a common type, see `Promote`. Element types without a common type (e.g. `String` and `&str`)
+//are not supported by the operators, `poly_dyadic` applies the operator directly.
 macro_rules!  ndarr_op{
+    ($Ty1:ty, $Ty2:ty, $Trait:tt, $F:tt, $Op:tt) => {
+
+        impl <T1, T2, R1: Unsigned, R2: Unsigned> $Trait<$Ty2> for $Ty1
+        where
+            R1: Max<R2>,
+            <R1 as Max<R2>>::Output: Unsigned,
+            T1: Clone + Debug + Default + Promote<T2>,
+            T2: Clone + Debug + Default,
+            Promoted<T1, T2>: $Trait<Output = Promoted<T1, T2>>,
+        {
+            type Output = Ndarr<Promoted<T1, T2>,Maximum<R1,R2>>;
+            fn $F(self, rhs: $Ty2) -> Self::Output {
+                self.poly_dyadic(&rhs, |x,y| {
+                    let (x, y) = x.promote(y);
+                    x $Op y
+                }).unwrap()
+            }
+        }
+    };
+}
+
+//Shifts are not promoted, the type of the left operand is preserved.
+macro_rules!  ndarr_shift_op{
     ($Ty1:ty, $Ty2:ty, $Trait:tt, $F:tt, $Op:tt) => {
 
         impl <T1, T2, T3, R1: Unsigned, R2: Unsigned> $Trait<$Ty2> for $Ty1
@@ -50,8 +75,71 @@ ndarr_op!(Ndarr<T1,R1>,  &Ndarr<T2,R2>, Rem, rem, %);
 ndarr_op!(&Ndarr<T1,R1>,  Ndarr<T2,R2>, Rem, rem, %);
 ndarr_op!(&Ndarr<T1,R1>, &Ndarr<T2,R2>, Rem, rem, %);
 
+//--------------------------------- BitAnd -----------------------------------
+ndarr_op!(Ndarr<T1,R1>,   Ndarr<T2,R2>, BitAnd, bitand, &);
+ndarr_op!(Ndarr<T1,R1>,  &Ndarr<T2,R2>, BitAnd, bitand, &);
+ndarr_op!(&Ndarr<T1,R1>,  Ndarr<T2,R2>, BitAnd, bitand, &);
+ndarr_op!(&Ndarr<T1,R1>, &Ndarr<T2,R2>, BitAnd, bitand, &);
+
+//--------------------------------- BitOr ------------------------------------
+ndarr_op!(Ndarr<T1,R1>,   Ndarr<T2,R2>, BitOr, bitor, |);
+ndarr_op!(Ndarr<T1,R1>,  &Ndarr<T2,R2>, BitOr, bitor, |);
+ndarr_op!(&Ndarr<T1,R1>,  Ndarr<T2,R2>, BitOr, bitor, |);
+ndarr_op!(&Ndarr<T1,R1>, &Ndarr<T2,R2>, BitOr, bitor, |);
+
+//--------------------------------- BitXor -----------------------------------
+ndarr_op!(Ndarr<T1,R1>,   Ndarr<T2,R2>, BitXor, bitxor, ^);
+ndarr_op!(Ndarr<T1,R1>,  &Ndarr<T2,R2>, BitXor, bitxor, ^);
+ndarr_op!(&Ndarr<T1,R1>,  Ndarr<T2,R2>, BitXor, bitxor, ^);
+ndarr_op!(&Ndarr<T1,R1>, &Ndarr<T2,R2>, BitXor, bitxor, ^);
+
+//--------------------------------- Shl --------------------------------------
+ndarr_shift_op!(Ndarr<T1,R1>,   Ndarr<T2,R2>, Shl, shl, <<);
+ndarr_shift_op!(Ndarr<T1,R1>,  &Ndarr<T2,R2>, Shl, shl, <<);
+ndarr_shift_op!(&Ndarr<T1,R1>,  Ndarr<T2,R2>, Shl, shl, <<);
+ndarr_shift_op!(&Ndarr<T1,R1>, &Ndarr<T2,R2>, Shl, shl, <<);
+
+//--------------------------------- Shr --------------------------------------
+ndarr_shift_op!(Ndarr<T1,R1>,   Ndarr<T2,R2>, Shr, shr, >>);
+ndarr_shift_op!(Ndarr<T1,R1>,  &Ndarr<T2,R2>, Shr, shr, >>);
+ndarr_shift_op!(&Ndarr<T1,R1>,  Ndarr<T2,R2>, Shr, shr, >>);
+ndarr_shift_op!(&Ndarr<T1,R1>, &Ndarr<T2,R2>, Shr, shr, >>);
+
 //////////////////////////////// Scalars ////////////////////////////////////
 macro_rules! scalar_op {
+    ($Op:tt, $f_name:tt, $f:tt) => {
+        impl<P, T, R: Unsigned> $Op<P> for Ndarr<T, R>
+        where
+            T: Clone + Debug + Default + PromoteScalar<P>,
+            P: Scalar + Copy,
+            PromotedScalar<T, P>: $Op<Output = PromotedScalar<T, P>>,
+        {
+            type Output = Ndarr<PromotedScalar<T, P>, R>;
+            fn $f_name(self, other: P) -> Self::Output {
+                self.map(|x| {
+                    let (x, y) = x.clone().promote_scalar(other);
+                    x $f y
+                })
+            }
+        }
+        impl<P, T, R: Unsigned> $Op<P> for &Ndarr<T, R>
+        where
+            T: Clone + Debug + Default + PromoteScalar<P>,
+            P: Scalar + Copy,
+            PromotedScalar<T, P>: $Op<Output = PromotedScalar<T, P>>,
+        {
+            type Output = Ndarr<PromotedScalar<T, P>, R>;
+            fn $f_name(self, other: P) -> Self::Output {
+                self.map(|x| {
+                    let (x, y) = x.clone().promote_scalar(other);
+                    x $f y
+                })
+            }
+        }
+    };
+}
+
+macro_rules! scalar_shift_op {
     ($Op:tt, $f_name:tt, $f:tt) => {
         impl<L,P, T, R: Unsigned> $Op<P> for Ndarr<T, R>
         where
@@ -83,23 +171,36 @@ scalar_op!(Sub, sub, -);
 scalar_op!(Mul, mul, *);
 scalar_op!(Div, div, /);
 scalar_op!(Rem, rem, %);
+scalar_op!(BitAnd, bitand, &);
+scalar_op!(BitOr, bitor, |);
+scalar_op!(BitXor, bitxor, ^);
+scalar_shift_op!(Shl, shl, <<);
+scalar_shift_op!(Shr, shr, >>);
 
 macro_rules! scalar_op2 {
     ($Op:tt, $f_name:tt, $f:tt, $t:ty) => {
         impl<T, R: Unsigned> $Op<Ndarr<T,R>> for $t
-            where T: Clone + Debug + Default + $Op<$t,Output = T>,
+            where T: Clone + Debug + Default + PromoteScalar<$t>,
+            PromotedScalar<T, $t>: $Op<Output = PromotedScalar<T, $t>>,
         {
-            type Output = Ndarr<T,R>;
+            type Output = Ndarr<PromotedScalar<T, $t>,R>;
             fn $f_name(self, rhs: Ndarr<T,R>) -> Self::Output {
-                rhs.map(|x| x.clone() $f self)
+                rhs.map(|x| {
+                    let (x, s) = x.clone().promote_scalar(self);
+                    s $f x
+                })
             }
         }
         impl<T, R: Unsigned> $Op<&Ndarr<T,R>> for $t
-            where T: Clone + Debug + Default + $Op<$t,Output = T>,
+            where T: Clone + Debug + Default + PromoteScalar<$t>,
+            PromotedScalar<T, $t>: $Op<Output = PromotedScalar<T, $t>>,
         {
-            type Output = Ndarr<T,R>;
+            type Output = Ndarr<PromotedScalar<T, $t>,R>;
             fn $f_name(self, rhs: &Ndarr<T,R>) -> Self::Output {
-                rhs.map(|x| x.clone() $f self)
+                rhs.map(|x| {
+                    let (x, s) = x.clone().promote_scalar(self);
+                    s $f x
+                })
             }
         }
     }
@@ -114,6 +215,14 @@ macro_rules! scalar_to_ndarr {
     };
 }
 
+macro_rules! scalar_to_ndarr_bits {
+    ($t:ty) => {
+        scalar_op2!(BitAnd, bitand, &, $t);
+        scalar_op2!(BitOr, bitor, |, $t);
+        scalar_op2!(BitXor, bitxor, ^, $t);
+    };
+}
+
 scalar_to_ndarr!(u8);
 scalar_to_ndarr!(u16);
 scalar_to_ndarr!(u32);
@@ -125,6 +234,18 @@ scalar_to_ndarr!(i32);
 scalar_to_ndarr!(i64);
 scalar_to_ndarr!(i128);
 
+scalar_to_ndarr_bits!(u8);
+scalar_to_ndarr_bits!(u16);
+scalar_to_ndarr_bits!(u32);
+scalar_to_ndarr_bits!(u64);
+scalar_to_ndarr_bits!(u128);
+scalar_to_ndarr_bits!(i8);
+scalar_to_ndarr_bits!(i16);
+scalar_to_ndarr_bits!(i32);
+scalar_to_ndarr_bits!(i64);
+scalar_to_ndarr_bits!(i128);
+scalar_to_ndarr_bits!(bool);
+
 scalar_to_ndarr!(f32);
 scalar_to_ndarr!(f64);
 
@@ -152,6 +273,28 @@ where
     }
 }
 
+//////////////////////////////////////////// Not /////////////////////////////////////////////
+
+impl<T, R: Unsigned> Not for Ndarr<T, R>
+where
+    T: Not<Output = T> + Clone + Debug + Default + Copy,
+{
+    type Output = Self;
+    fn not(self) -> Self::Output {
+        self.map(|x| !*x)
+    }
+}
+
+impl<T, R: Unsigned> Not for &Ndarr<T, R>
+where
+    T: Not<Output = T> + Clone + Debug + Default + Copy,
+{
+    type Output = Ndarr<T, R>;
+    fn not(self) -> Self::Output {
+        self.map(|x| !*x)
+    }
+}
+
 //////////////////////////////////////////// AddAssing /////////////////////////////////////////////
 
 impl<P, T, R: Unsigned> AddAssign<&P> for Ndarr<T, R>
@@ -211,6 +354,42 @@ where
         self.bimap_in_place(&other.into_ndarr(&self.dim), |x, y| x % y)
     }
 }
+////////////////////////////////////////////  BitAndAssing /////////////////////////////////////////////
+
+impl<P, T, R: Unsigned> BitAndAssign<&P> for Ndarr<T, R>
+where
+    T: BitAnd<Output = T> + Clone + Debug + Default,
+    P: IntoNdarr<T, R> + Clone,
+{
+    fn bitand_assign(&mut self, other: &P) {
+        self.bimap_in_place(&other.into_ndarr(&self.dim), |x, y| x & y)
+    }
+}
+
+////////////////////////////////////////////  BitOrAssing /////////////////////////////////////////////
+
+impl<P, T, R: Unsigned> BitOrAssign<&P> for Ndarr<T, R>
+where
+    T: BitOr<Output = T> + Clone + Debug + Default,
+    P: IntoNdarr<T, R> + Clone,
+{
+    fn bitor_assign(&mut self, other: &P) {
+        self.bimap_in_place(&other.into_ndarr(&self.dim), |x, y| x | y)
+    }
+}
+
+////////////////////////////////////////////  BitXorAssing /////////////////////////////////////////////
+
+impl<P, T, R: Unsigned> BitXorAssign<&P> for Ndarr<T, R>
+where
+    T: BitXor<Output = T> + Clone + Debug + Default,
+    P: IntoNdarr<T, R> + Clone,
+{
+    fn bitxor_assign(&mut self, other: &P) {
+        self.bimap_in_place(&other.into_ndarr(&self.dim), |x, y| x ^ y)
+    }
+}
+
 #[cfg(test)]
 mod test_arithmetics {
     use super::*;
@@ -247,4 +426,83 @@ mod test_arithmetics {
         let scalar_arr = 2.0 * arr;
         assert_eq!(arr_scalar, scalar_arr)
     }
+
+    #[test]
+    fn test_logical() {
+        let a = Ndarr::from([true, true, false, false]);
+        let b = Ndarr::from([true, false, true, false]);
+        assert_eq!(&a & &b, Ndarr::from([true, false, false, false]));
+        assert_eq!(&a | &b, Ndarr::from([true, true, true, false]));
+        assert_eq!(&a ^ &b, Ndarr::from([false, true, true, false]));
+        assert_eq!(!a, Ndarr::from([false, false, true, true]));
+        assert_eq!(&b & true, b);
+        assert_eq!(false | &b, b);
+    }
+
+    #[test]
+    fn test_bitwise() {
+        let a = Ndarr::from([[0b1100, 0b1010], [0b0110, 0b0001]]);
+        let b = Ndarr::from([0b1010, 0b0011]);
+        assert_eq!(&a & &b, Ndarr::from([[0b1000, 0b0010], [0b0010, 0b0001]]));
+        assert_eq!(&a | &b, Ndarr::from([[0b1110, 0b1011], [0b1110, 0b0011]]));
+        assert_eq!(
+            &a ^ 0b1111,
+            Ndarr::from([[0b0011, 0b0101], [0b1001, 0b1110]])
+        );
+        assert_eq!(!Ndarr::from([0u8, 255]), Ndarr::from([255u8, 0]));
+        assert_eq!(&b << 1, Ndarr::from([0b10100, 0b0110]));
+        assert_eq!(&b >> Ndarr::from([1, 0]), Ndarr::from([0b0101, 0b0011]));
+    }
+
+    #[test]
+    fn test_bit_assign() {
+        let mut arr = Ndarr::from([0b1100, 0b1010]);
+        arr &= &0b1000;
+        arr |= &Ndarr::from([0b0001, 0b0010]);
+        arr ^= &0b0011;
+        assert_eq!(arr, Ndarr::from([0b1010, 0b1001]))
+    }
+
+    #[test]
+    fn test_promotion() {
+        let a = Ndarr::from([1, 2, 3]);
+        let b = Ndarr::from([0.5, 0.5, 0.5]);
+        let r: Ndarr<f64, U1> = &a + &b;
+        assert_eq!(r, Ndarr::from([1.5, 2.5, 3.5]));
+        let pixels = Ndarr::from([[10u8, 20], [30, 40]]);
+        let r: Ndarr<f32, U2> = &pixels * 0.5f32;
+        assert_eq!(r, Ndarr::from([[5., 10.], [15., 20.]]));
+        let r: Ndarr<i16, U1> = Ndarr::from([200u8, 0]) + Ndarr::from([-1i8, -100]);
+        assert_eq!(r, Ndarr::from([199, -100]));
+        #[cfg(feature = "complex")]
+        {
+            let z: Ndarr<C<f64>, U1> = Ndarr::from([1f32, 2.]) + Ndarr::from([C(0., 1.)]);
+            assert_eq!(z, Ndarr::from([C(1., 1.), C(2., 1.)]));
+        }
+    }
+
+    #[test]
+    fn test_weak_scalars() {
+        let a = Ndarr::from([1f32, 2.]);
+        let r: Ndarr<f32, U1> = &a * 2.0;
+        assert_eq!(r, Ndarr::from([2., 4.]));
+        let r: Ndarr<u8, U1> = Ndarr::from([1u8, 2]) + 1;
+        assert_eq!(r, Ndarr::from([2, 3]));
+        let r: Ndarr<f64, U1> = Ndarr::from([1, 2]) * 1.5;
+        assert_eq!(r, Ndarr::from([1.5, 3.]));
+        assert_eq!(1 - Ndarr::from([1, 2]), Ndarr::from([0, -1]));
+        assert_eq!(10 / Ndarr::from([2, 5]), Ndarr::from([5, 2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit the array element type u8")]
+    fn test_scalar_out_of_range_unsigned() {
+        let _ = Ndarr::from([10u8, 20]) + 300;
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit the array element type i8")]
+    fn test_scalar_out_of_range_signed() {
+        let _ = Ndarr::from([1i8, 2]) * -200;
+    }
 }
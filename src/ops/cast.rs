@@ -0,0 +1,164 @@
+use super::*;
+use num_traits::{AsPrimitive, Bounded, NumCast, Zero};
+use std::any::type_name;
+
+//Casts a single value, saturating to the bounds of `U` when it does not fit and mapping NaN to zero.
+fn saturate<T, U>(x: T) -> U
+where
+    T: NumCast + PartialOrd + Zero + Copy,
+    U: NumCast + Bounded + Zero,
+{
+    U::from(x).unwrap_or_else(|| {
+        if x < T::zero() {
+            U::min_value()
+        } else if x > T::zero() {
+            U::max_value()
+        } else {
+            U::zero()
+        }
+    })
+}
+
+fn checked<T, U>(x: T) -> Result<U, CastError>
+where
+    T: NumCast + Copy + Debug,
+    U: NumCast,
+{
+    U::from(x).ok_or_else(|| {
+        CastError::new(&format!(
+            "Value {:?} of type {} can not be represented as {}.",
+            x,
+            type_name::<T>(),
+            type_name::<U>()
+        ))
+    })
+}
+
+impl<T, R: Unsigned> Ndarr<T, R>
+where
+    T: Clone + Copy + Debug + NumCast,
+{
+    ///Casts every element to type `U`. Returns Error if any value can not be represented in `U`, e.g. an overflow or a NaN to integer conversion.
+    pub fn cast<U: Clone + Debug + NumCast>(&self) -> Result<Ndarr<U, R>, CastError> {
+        let data = self
+            .data
+            .iter()
+            .map(|x| checked(*x))
+            .collect::<Result<Vec<U>, CastError>>()?;
+        Ok(Ndarr {
+            data,
+            dim: self.dim.clone(),
+        })
+    }
+
+    ///Casts every element to type `U`, values out of range are clamped to the bounds of `U` and NaN is mapped to zero.
+    pub fn cast_saturating<U>(&self) -> Ndarr<U, R>
+    where
+        T: PartialOrd + Zero,
+        U: Clone + Debug + NumCast + Bounded + Zero,
+    {
+        self.map(|x| saturate(*x))
+    }
+
+    ///Casts every element to type `U` with the same semantics as the `as` keyword.
+    pub fn cast_lossy<U>(&self) -> Ndarr<U, R>
+    where
+        T: AsPrimitive<U>,
+        U: Clone + Debug + Copy + 'static,
+    {
+        self.map(|x| x.as_())
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T, R: Unsigned> Ndarr<C<T>, R>
+where
+    T: Clone + Copy + Debug + PartialEq + NumCast,
+{
+    ///Casts the real and imaginary parts of every element to type `U`. Returns Error if any part can not be represented in `U`.
+    pub fn cast<U>(&self) -> Result<Ndarr<C<U>, R>, CastError>
+    where
+        U: Clone + Copy + Debug + PartialEq + NumCast,
+    {
+        let data = self
+            .data
+            .iter()
+            .map(|z| Ok(C(checked(z.0)?, checked(z.1)?)))
+            .collect::<Result<Vec<C<U>>, CastError>>()?;
+        Ok(Ndarr {
+            data,
+            dim: self.dim.clone(),
+        })
+    }
+
+    ///Casts the real and imaginary parts of every element to type `U`, clamping values out of range to the bounds of `U`.
+    pub fn cast_saturating<U>(&self) -> Ndarr<C<U>, R>
+    where
+        T: PartialOrd + Zero,
+        U: Clone + Copy + Debug + PartialEq + NumCast + Bounded + Zero,
+    {
+        self.map(|z| C(saturate(z.0), saturate(z.1)))
+    }
+
+    ///Casts every element to `C<U>` with the same semantics as the `as` keyword on each part, e.g. `C<f64>` to `C<f32>`.
+    pub fn cast_lossy<U>(&self) -> Ndarr<C<U>, R>
+    where
+        U: Clone + Copy + Debug + PartialEq,
+        C<U>: From<C<T>>,
+    {
+        self.map(|z| C::<U>::from(*z))
+    }
+}
+
+#[cfg(test)]
+mod test_cast {
+    use super::*;
+
+    #[test]
+    fn checked() {
+        let a = Ndarr::from([1, -2, 300]);
+        assert_eq!(a.cast::<f64>().unwrap(), Ndarr::from([1., -2., 300.]));
+        assert!(a.cast::<u8>().is_err());
+        assert!(a.cast::<i16>().is_ok());
+        assert!(Ndarr::from([f64::NAN]).cast::<i32>().is_err());
+        assert_eq!(
+            Ndarr::from([1.7, -1.7]).cast::<i32>().unwrap(),
+            Ndarr::from([1, -1])
+        );
+    }
+
+    #[test]
+    fn saturating() {
+        let a = Ndarr::from([-5, 100, 1000]);
+        assert_eq!(a.cast_saturating::<u8>(), Ndarr::from([0u8, 100, 255]));
+        let b = Ndarr::from([f32::NAN, 1e10, -1e10]);
+        assert_eq!(
+            b.cast_saturating::<i32>(),
+            Ndarr::from([0, i32::MAX, i32::MIN])
+        );
+    }
+
+    #[test]
+    fn lossy() {
+        let a = Ndarr::from([255u8, 1]);
+        assert_eq!(a.cast_lossy::<i8>(), Ndarr::from([-1i8, 1]));
+        assert_eq!(Ndarr::from([2.9]).cast_lossy::<u8>(), Ndarr::from([2u8]));
+    }
+
+    #[test]
+    #[cfg(feature = "complex")]
+    fn complex() {
+        let z = Ndarr::from([C(1.5f64, -2.0), C(0.25, 1e300)]);
+        assert_eq!(
+            z.cast_lossy::<f32>(),
+            Ndarr::from([C(1.5f32, -2.0), C(0.25, f32::INFINITY)])
+        );
+        let w = Ndarr::from([C(1.5f32, -2.0)]);
+        assert_eq!(w.cast_lossy::<f64>(), Ndarr::from([C(1.5f64, -2.0)]));
+        assert_eq!(
+            Ndarr::from([C(1, 300)]).cast_saturating::<u8>(),
+            Ndarr::from([C(1u8, 255)])
+        );
+        assert!(Ndarr::from([C(1, 300)]).cast::<u8>().is_err());
+    }
+}
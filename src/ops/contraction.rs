@@ -0,0 +1,366 @@
+use super::*;
+use std::collections::HashMap;
+use std::ops::{Add, Mul};
+
+use crate::helpers::multiply_list;
+
+//Labels used internally to identify the axes of the operands of a contraction.
+type Labels = Vec<usize>;
+
+//Contracts one or two operands, multiplying the elements and summing over all the labels not present in `out`.
+//Repeated labels inside the same operand take the diagonal.
+fn contract<T>(
+    operands: &[(&[usize], &Ndarr<T, UTerm>)],
+    out: &[usize],
+    sizes: &HashMap<usize, usize>,
+) -> Ndarr<T, UTerm>
+where
+    T: Clone + Debug + Default + Add<Output = T> + Mul<Output = T>,
+{
+    let mut loop_labels = out.to_vec();
+    for (labels, _) in operands {
+        for l in labels.iter() {
+            if !loop_labels.contains(l) {
+                loop_labels.push(*l);
+            }
+        }
+    }
+    let dims: Vec<usize> = loop_labels.iter().map(|l| sizes[l]).collect();
+    //stride of each loop label in each operand
+    let strides: Vec<Vec<usize>> = operands
+        .iter()
+        .map(|(labels, arr)| {
+            let shape = arr.shape();
+            loop_labels
+                .iter()
+                .map(|l| {
+                    (0..labels.len())
+                        .filter(|p| labels[*p] == *l)
+                        .map(|p| multiply_list(&shape[p + 1..], 1))
+                        .sum()
+                })
+                .collect()
+        })
+        .collect();
+    let out_shape: Vec<usize> = dims[..out.len()].to_vec();
+    let n_out = multiply_list(&out_shape, 1);
+    let n_sum = multiply_list(&dims[out.len()..], 1);
+    let mut data = vec![T::default(); n_out];
+    if n_out * n_sum > 0 {
+        let r = dims.len();
+        let mut idx = vec![0; r];
+        let mut offsets = vec![0; operands.len()];
+        for it in 0..n_out * n_sum {
+            let mut prod = operands[0].1.data[offsets[0]].clone();
+            for k in 1..operands.len() {
+                prod = prod * operands[k].1.data[offsets[k]].clone();
+            }
+            let o = it / n_sum;
+            data[o] = data[o].clone() + prod;
+            //advance the multi-index
+            for d in (0..r).rev() {
+                idx[d] += 1;
+                for (k, off) in offsets.iter_mut().enumerate() {
+                    *off += strides[k][d];
+                }
+                if idx[d] < dims[d] {
+                    break;
+                }
+                for (k, off) in offsets.iter_mut().enumerate() {
+                    *off -= strides[k][d] * dims[d];
+                }
+                idx[d] = 0;
+            }
+        }
+    }
+    Ndarr {
+        data,
+        dim: Dim::new(&out_shape).unwrap(),
+    }
+}
+
+//Records the size of every label, returns Error if the same label has different sizes.
+fn register_sizes<R: Unsigned, T: Clone>(
+    labels: &[usize],
+    arr: &Ndarr<T, R>,
+    sizes: &mut HashMap<usize, usize>,
+) -> Result<(), DimError> {
+    if labels.len() != arr.rank() {
+        return Err(DimError::new(&format!(
+            "Operand of shape {:?} does not match a subscript of length {}.",
+            arr.shape(),
+            labels.len()
+        )));
+    }
+    for (l, s) in labels.iter().zip(arr.shape()) {
+        match sizes.insert(*l, *s) {
+            Some(prev) if prev != *s => {
+                return Err(DimError::new(&format!(
+                    "Contracted axes have different lengths {} and {}.",
+                    prev, s
+                )))
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+///Tensor contraction that sums the products of the elements of `a` and `b` over the axes `axes_a` of `a` paired with the axes `axes_b` of `b`.
+///The resulting axes are the remaining axes of `a` followed by the remaining axes of `b`.
+///```
+///use rapl::*;
+///let a = Ndarr::from([[1, 2], [3, 4]]);
+///let b = Ndarr::from([[5, 6], [7, 8]]);
+///let c = ops::tensordot(&a, &b, &[1], &[0]).unwrap();
+///assert_eq!(c.into_rank::<U2>().unwrap(), a.mat_mul(&b));
+///```
+pub fn tensordot<T, R1: Unsigned, R2: Unsigned>(
+    a: &Ndarr<T, R1>,
+    b: &Ndarr<T, R2>,
+    axes_a: &[usize],
+    axes_b: &[usize],
+) -> Result<Ndarr<T, UTerm>, DimError>
+where
+    T: Clone + Debug + Default + Add<Output = T> + Mul<Output = T>,
+{
+    let (r1, r2) = (a.rank(), b.rank());
+    let mut unique_a = axes_a.to_vec();
+    let mut unique_b = axes_b.to_vec();
+    unique_a.sort();
+    unique_a.dedup();
+    unique_b.sort();
+    unique_b.dedup();
+    if axes_a.len() != axes_b.len()
+        || unique_a.len() != axes_a.len()
+        || unique_b.len() != axes_b.len()
+        || axes_a.iter().any(|x| *x >= r1)
+        || axes_b.iter().any(|x| *x >= r2)
+    {
+        return Err(DimError::new(&format!(
+            "Invalid contraction axes {:?} and {:?} for arrays of rank {} and {}.",
+            axes_a, axes_b, r1, r2
+        )));
+    }
+    let labels_a: Labels = (0..r1).collect();
+    let labels_b: Labels = (0..r2)
+        .map(|j| match axes_b.iter().position(|x| *x == j) {
+            Some(p) => axes_a[p],
+            None => r1 + j,
+        })
+        .collect();
+    let mut sizes = HashMap::new();
+    register_sizes(&labels_a, a, &mut sizes)?;
+    register_sizes(&labels_b, b, &mut sizes)?;
+    let out: Labels = labels_a
+        .iter()
+        .filter(|l| !axes_a.contains(l))
+        .chain(labels_b.iter().filter(|l| **l >= r1))
+        .cloned()
+        .collect();
+    let a = a.clone().into_notyped();
+    let b = b.clone().into_notyped();
+    Ok(contract(&[(&labels_a, &a), (&labels_b, &b)], &out, &sizes))
+}
+
+//Parses a subscript like "ij" into labels.
+fn parse_subscript(s: &str) -> Result<Labels, DimError> {
+    s.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| {
+            if c.is_ascii_alphabetic() {
+                Ok(c as usize)
+            } else {
+                Err(DimError::new(&format!(
+                    "Invalid character '{}' in einsum subscript.",
+                    c
+                )))
+            }
+        })
+        .collect()
+}
+
+///Einstein summation over the operands as described by `spec`, e.g. `"ij,jk->ik"` for matrix multiplication, `"ii->"` for the trace
+///or `"i,j->ij"` for the outer product. Labels are single ascii letters. Without `->` the output has the labels that appear only once, in alphabetical order.
+///Operands are contracted in pairs, choosing at each step the pair with the smallest number of operations.
+///```
+///use rapl::*;
+///let a = Ndarr::from([[1, 2], [3, 4]]).into_notyped();
+///let b = Ndarr::from([1, 1]).into_notyped();
+///let c = ops::einsum("ij,j->i", &[a, b]).unwrap();
+///assert_eq!(c.into_rank::<U1>().unwrap(), Ndarr::from([3, 7]));
+///```
+pub fn einsum<T>(spec: &str, operands: &[Ndarr<T, UTerm>]) -> Result<Ndarr<T, UTerm>, DimError>
+where
+    T: Clone + Debug + Default + Add<Output = T> + Mul<Output = T>,
+{
+    let (inputs, output) = match spec.split_once("->") {
+        Some((i, o)) => (i, Some(o)),
+        None => (spec, None),
+    };
+    let terms = inputs
+        .split(',')
+        .map(parse_subscript)
+        .collect::<Result<Vec<Labels>, DimError>>()?;
+    if terms.len() != operands.len() {
+        return Err(DimError::new(&format!(
+            "Einsum spec has {} operands but {} were provided.",
+            terms.len(),
+            operands.len()
+        )));
+    }
+    let mut sizes = HashMap::new();
+    for (labels, arr) in terms.iter().zip(operands) {
+        register_sizes(labels, arr, &mut sizes)?;
+    }
+    let count = |l: &usize| terms.iter().flatten().filter(|x| *x == l).count();
+    let out = match output {
+        Some(o) => {
+            let out = parse_subscript(o)?;
+            for (i, l) in out.iter().enumerate() {
+                if !sizes.contains_key(l) || out[..i].contains(l) {
+                    return Err(DimError::new(&format!(
+                        "Invalid output subscript '{}' in einsum.",
+                        o
+                    )));
+                }
+            }
+            out
+        }
+        None => {
+            let mut out: Labels = sizes.keys().filter(|l| count(l) == 1).cloned().collect();
+            out.sort();
+            out
+        }
+    };
+
+    let mut remaining: Vec<(Labels, Ndarr<T, UTerm>)> =
+        terms.into_iter().zip(operands.iter().cloned()).collect();
+    //labels of the output and of every operand except `skip`
+    let needed = |remaining: &Vec<(Labels, Ndarr<T, UTerm>)>, skip: &[usize]| -> Labels {
+        let mut needed = out.clone();
+        for (k, (labels, _)) in remaining.iter().enumerate() {
+            if !skip.contains(&k) {
+                needed.extend(labels);
+            }
+        }
+        needed
+    };
+    //keeps the labels of `labels` that are needed, without repetitions
+    let keep = |labels: &[usize], needed: &Labels| -> Labels {
+        let mut kept = Labels::new();
+        for l in labels {
+            if needed.contains(l) && !kept.contains(l) {
+                kept.push(*l);
+            }
+        }
+        kept
+    };
+
+    while remaining.len() > 1 {
+        //greedily choose the pair with the cheapest contraction
+        let mut best = (0, 1, usize::MAX);
+        for i in 0..remaining.len() {
+            for j in i + 1..remaining.len() {
+                let mut union = remaining[i].0.clone();
+                union.extend(&remaining[j].0);
+                union.sort();
+                union.dedup();
+                let cost = union.iter().map(|l| sizes[l]).product();
+                if cost < best.2 {
+                    best = (i, j, cost);
+                }
+            }
+        }
+        let (i, j, _) = best;
+        let needed = needed(&remaining, &[i, j]);
+        let mut union = remaining[i].0.clone();
+        union.extend(&remaining[j].0);
+        let kept = keep(&union, &needed);
+        let (lj, bj) = remaining.remove(j);
+        let (li, bi) = remaining.remove(i);
+        let res = contract(&[(&li, &bi), (&lj, &bj)], &kept, &sizes);
+        remaining.push((kept, res));
+    }
+    let (labels, arr) = &remaining[0];
+    Ok(contract(&[(labels, arr)], &out, &sizes))
+}
+
+#[cfg(test)]
+mod contraction_test {
+    use super::*;
+
+    #[test]
+    fn tensordot_axes() {
+        let a = Ndarr::from(0..24).reshape([2, 3, 4]).unwrap();
+        let b = Ndarr::from(0..12).reshape([4, 3]).unwrap();
+        //contract axes 1 and 2 of a with axes 1 and 0 of b
+        let c = tensordot(&a, &b, &[1, 2], &[1, 0]).unwrap();
+        let mut expected = vec![0; 2];
+        for (i, e) in expected.iter_mut().enumerate() {
+            for j in 0..3 {
+                for k in 0..4 {
+                    *e += a[[i, j, k]] * b[[k, j]];
+                }
+            }
+        }
+        assert_eq!(c.into_rank::<U1>().unwrap(), Ndarr::from(expected));
+        let outer = tensordot(&Ndarr::from([1, 2]), &Ndarr::from([3, 4]), &[], &[]).unwrap();
+        assert_eq!(
+            outer.into_rank::<U2>().unwrap(),
+            Ndarr::from([[3, 4], [6, 8]])
+        );
+        assert!(tensordot(&a, &b, &[0], &[0]).is_err());
+        assert!(tensordot(&a, &b, &[1, 1], &[1, 0]).is_err());
+    }
+
+    #[test]
+    fn einsum_specs() {
+        let a = Ndarr::from(1..7).reshape([2, 3]).unwrap();
+        let b = Ndarr::from(1..7).reshape([3, 2]).unwrap();
+        let mm = einsum(
+            "ij,jk->ik",
+            &[a.clone().into_notyped(), b.clone().into_notyped()],
+        )
+        .unwrap();
+        assert_eq!(mm.into_rank::<U2>().unwrap(), a.mat_mul(&b));
+        let implicit = einsum("ij,jk", &[a.clone().into_notyped(), b.into_notyped()]).unwrap();
+        assert_eq!(implicit.shape(), &[2, 2]);
+        let t = einsum("ij->ji", &[a.clone().into_notyped()]).unwrap();
+        assert_eq!(t.into_rank::<U2>().unwrap(), a.t());
+        let m = Ndarr::from([[1, 2], [3, 4]]).into_notyped();
+        assert_eq!(
+            einsum("ii->", std::slice::from_ref(&m)).unwrap().data,
+            vec![5]
+        );
+        assert_eq!(
+            einsum("ii->i", std::slice::from_ref(&m)).unwrap().data,
+            vec![1, 4]
+        );
+        assert_eq!(
+            einsum("ij->", std::slice::from_ref(&m)).unwrap().data,
+            vec![10]
+        );
+        assert!(einsum("ij,jk->ik", std::slice::from_ref(&m)).is_err());
+        assert!(einsum("ijk->", std::slice::from_ref(&m)).is_err());
+        assert!(einsum("ij->k", &[m]).is_err());
+    }
+
+    #[test]
+    fn einsum_chain() {
+        let a = Ndarr::from(0..6).reshape([2, 3]).unwrap();
+        let b = Ndarr::from(0..12).reshape([3, 4]).unwrap();
+        let c = Ndarr::from(0..4);
+        let abc = einsum(
+            "ij,jk,k->i",
+            &[
+                a.clone().into_notyped(),
+                b.clone().into_notyped(),
+                c.clone().into_notyped(),
+            ],
+        )
+        .unwrap();
+        let expected = a.mat_mul(&b).mat_mul(&c);
+        assert_eq!(abc.into_rank::<U1>().unwrap(), expected);
+    }
+}
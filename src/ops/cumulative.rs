@@ -0,0 +1,222 @@
+use super::*;
+use std::ops::{Add, Mul, Sub};
+
+use crate::helpers::multiply_list;
+
+impl<T: Clone + Debug, R: Unsigned> Ndarr<T, R> {
+    //Running accumulation of `f` along an axis, starting from the first element.
+    fn accumulate<F: Fn(T, T) -> T>(&self, axis: usize, f: F) -> Result<Self, DimError> {
+        let len = *self.shape().get(axis).unwrap_or(&0);
+        self.map_lanes(axis, len, |lane| {
+            let mut out: Vec<T> = Vec::with_capacity(lane.len());
+            for x in lane {
+                let next = match out.last() {
+                    Some(acc) => f(acc.clone(), x),
+                    None => x,
+                };
+                out.push(next);
+            }
+            out
+        })
+    }
+
+    ///Cumulative sum along an axis.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1, 2, 3], [4, 5, 6]]);
+    ///assert_eq!(a.cumsum(1).unwrap(), Ndarr::from([[1, 3, 6], [4, 9, 15]]));
+    ///```
+    pub fn cumsum(&self, axis: usize) -> Result<Self, DimError>
+    where
+        T: Add<Output = T>,
+    {
+        self.accumulate(axis, |x, y| x + y)
+    }
+
+    ///Cumulative product along an axis.
+    pub fn cumprod(&self, axis: usize) -> Result<Self, DimError>
+    where
+        T: Mul<Output = T>,
+    {
+        self.accumulate(axis, |x, y| x * y)
+    }
+
+    ///Cumulative maximum along an axis.
+    pub fn cummax(&self, axis: usize) -> Result<Self, DimError>
+    where
+        T: PartialOrd,
+    {
+        self.accumulate(axis, |x, y| if y > x { y } else { x })
+    }
+
+    ///Cumulative minimum along an axis.
+    pub fn cummin(&self, axis: usize) -> Result<Self, DimError>
+    where
+        T: PartialOrd,
+    {
+        self.accumulate(axis, |x, y| if y < x { y } else { x })
+    }
+
+    //Concatenates arrays along an axis, all the other axes must have the same length.
+    fn concat_axis(arrays: &[&Self], axis: usize) -> Result<Self, DimError> {
+        let shape = arrays[0].shape();
+        if axis >= shape.len() {
+            return Err(DimError::new("Axis grater than rank"));
+        }
+        for a in arrays {
+            if a.rank() != shape.len()
+                || (0..shape.len()).any(|i| i != axis && a.shape()[i] != shape[i])
+            {
+                return Err(DimError::new(&format!(
+                    "Can not concatenate arrays of shape {:?} and {:?} along axis {}.",
+                    shape,
+                    a.shape(),
+                    axis
+                )));
+            }
+        }
+        let outer = multiply_list(&shape[..axis], 1);
+        let inner = multiply_list(&shape[axis + 1..], 1);
+        let mut data = Vec::new();
+        for o in 0..outer {
+            for a in arrays {
+                let block = a.shape()[axis] * inner;
+                data.extend_from_slice(&a.data[o * block..(o + 1) * block]);
+            }
+        }
+        let mut new_shape = shape.to_vec();
+        new_shape[axis] = arrays.iter().map(|a| a.shape()[axis]).sum();
+        Ok(Ndarr {
+            data,
+            dim: Dim::new(&new_shape)?,
+        })
+    }
+
+    ///The `n`-th discrete difference along an axis, `out[i] = a[i + 1] - a[i]` applied `n` times.
+    ///The optional `prepend` and `append` arrays are concatenated along the axis before taking the differences.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([1, 4, 9, 16]);
+    ///assert_eq!(a.diff(1, 0, None, None).unwrap(), Ndarr::from([3, 5, 7]));
+    ///assert_eq!(a.diff(2, 0, None, None).unwrap(), Ndarr::from([2, 2]));
+    ///assert_eq!(a.diff(1, 0, Some(&Ndarr::from([0])), None).unwrap(), Ndarr::from([1, 3, 5, 7]));
+    ///```
+    pub fn diff(
+        &self,
+        n: usize,
+        axis: usize,
+        prepend: Option<&Self>,
+        append: Option<&Self>,
+    ) -> Result<Self, DimError>
+    where
+        T: Sub<Output = T>,
+    {
+        let mut arrays = Vec::with_capacity(3);
+        arrays.extend(prepend);
+        arrays.push(self);
+        arrays.extend(append);
+        let mut out = Self::concat_axis(&arrays, axis)?;
+        for _ in 0..n {
+            let len = out.shape()[axis];
+            if len == 0 {
+                break;
+            }
+            out = out.map_lanes(axis, len - 1, |lane| {
+                lane.windows(2)
+                    .map(|w| w[1].clone() - w[0].clone())
+                    .collect()
+            })?;
+        }
+        Ok(out)
+    }
+}
+
+impl<T: Float + Clone + Debug, R: Unsigned> Ndarr<T, R> {
+    ///Gradient along each axis with uniform `spacing`, using second order central differences in the interior
+    ///and first order one-sided differences at the boundaries. Returns one array per axis.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([1., 2., 4., 7., 11.]);
+    ///assert_eq!(a.gradient(1.).unwrap()[0], Ndarr::from([1., 1.5, 2.5, 3.5, 4.]));
+    ///```
+    pub fn gradient(&self, spacing: T) -> Result<Vec<Self>, DimError> {
+        let two = T::one() + T::one();
+        (0..self.rank())
+            .map(|axis| {
+                let len = self.shape()[axis];
+                if len < 2 {
+                    return Err(DimError::new(&format!(
+                        "Can not compute the gradient along axis {} of length {}.",
+                        axis, len
+                    )));
+                }
+                self.map_lanes(axis, len, |f| {
+                    (0..len)
+                        .map(|i| {
+                            if i == 0 {
+                                (f[1] - f[0]) / spacing
+                            } else if i == len - 1 {
+                                (f[i] - f[i - 1]) / spacing
+                            } else {
+                                (f[i + 1] - f[i - 1]) / (two * spacing)
+                            }
+                        })
+                        .collect()
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod cumulative_test {
+    use super::*;
+
+    #[test]
+    fn cumulative() {
+        let a = Ndarr::from([[3, 1, 4], [1, 5, 9]]);
+        assert_eq!(a.cumsum(0).unwrap(), Ndarr::from([[3, 1, 4], [4, 6, 13]]));
+        assert_eq!(a.cumprod(1).unwrap(), Ndarr::from([[3, 3, 12], [1, 5, 45]]));
+        assert_eq!(a.cummax(1).unwrap(), Ndarr::from([[3, 3, 4], [1, 5, 9]]));
+        assert_eq!(a.cummin(1).unwrap(), Ndarr::from([[3, 1, 1], [1, 1, 1]]));
+        assert!(a.cumsum(2).is_err());
+        let empty = Ndarr::<i32, U2>::new(&[], [2, 0]).unwrap();
+        assert_eq!(empty.cumsum(1).unwrap(), empty);
+    }
+
+    #[test]
+    fn diff() {
+        let a = Ndarr::from([[1, 3, 6], [2, 2, 0]]);
+        assert_eq!(
+            a.diff(1, 1, None, None).unwrap(),
+            Ndarr::from([[2, 3], [0, -2]])
+        );
+        assert_eq!(
+            a.diff(1, 0, None, None).unwrap(),
+            Ndarr::from([[1, -1, -6]])
+        );
+        assert_eq!(a.diff(0, 0, None, None).unwrap(), a);
+        assert_eq!(a.diff(5, 1, None, None).unwrap().shape(), &[2, 0]);
+        let edge = Ndarr::from([[0], [0]]);
+        assert_eq!(
+            a.diff(1, 1, Some(&edge), Some(&edge)).unwrap(),
+            Ndarr::from([[1, 2, 3, -6], [2, 0, -2, 0]])
+        );
+        assert!(a.diff(1, 1, Some(&Ndarr::from([[0, 0, 0]])), None).is_err());
+    }
+
+    #[test]
+    fn gradient() {
+        let x = Ndarr::from([[0., 1., 4.], [1., 2., 5.], [4., 5., 8.]]);
+        let g = x.gradient(0.5).unwrap();
+        assert_eq!(
+            g[0],
+            Ndarr::from([[2., 2., 2.], [4., 4., 4.], [6., 6., 6.]])
+        );
+        assert_eq!(
+            g[1],
+            Ndarr::from([[2., 4., 6.], [2., 4., 6.], [2., 4., 6.]])
+        );
+        assert!(Ndarr::from([1.]).gradient(1.).is_err());
+    }
+}
@@ -45,6 +45,176 @@ where
     pub fn log2(&self) -> Self {
         self.map(|x| x.log2())
     }
+
+    pub fn log10(&self) -> Self {
+        self.map(|x| x.log10())
+    }
+
+    pub fn exp2(&self) -> Self {
+        self.map(|x| x.exp2())
+    }
+
+    ///Returns `exp(x) - 1` element wise, accurate even if `x` is close to zero.
+    pub fn expm1(&self) -> Self {
+        self.map(|x| x.exp_m1())
+    }
+
+    ///Same as `expm1`, named after `f64::exp_m1` of the standard library.
+    pub fn exp_m1(&self) -> Self {
+        self.expm1()
+    }
+
+    ///Returns `ln(1 + x)` element wise, more accurate than `(x + 1).ln()` when `x` is close to zero.
+    pub fn ln_1p(&self) -> Self {
+        self.map(|x| x.ln_1p())
+    }
+
+    pub fn sqrt(&self) -> Self {
+        self.map(|x| x.sqrt())
+    }
+
+    pub fn cbrt(&self) -> Self {
+        self.map(|x| x.cbrt())
+    }
+
+    pub fn powi(&self, n: i32) -> Self {
+        self.map(|x| x.powi(n))
+    }
+
+    pub fn powf(&self, n: T) -> Self {
+        self.map(|x| x.powf(n))
+    }
+
+    pub fn recip(&self) -> Self {
+        self.map(|x| x.recip())
+    }
+
+    ///Fused multiply-add, computes `(x * a) + b` element wise with only one rounding error.
+    pub fn mul_add(&self, a: T, b: T) -> Self {
+        self.map(|x| x.mul_add(a, b))
+    }
+
+    pub fn asin(&self) -> Self {
+        self.map(|x| x.asin())
+    }
+
+    pub fn acos(&self) -> Self {
+        self.map(|x| x.acos())
+    }
+
+    pub fn atan(&self) -> Self {
+        self.map(|x| x.atan())
+    }
+
+    pub fn asinh(&self) -> Self {
+        self.map(|x| x.asinh())
+    }
+
+    pub fn acosh(&self) -> Self {
+        self.map(|x| x.acosh())
+    }
+
+    pub fn atanh(&self) -> Self {
+        self.map(|x| x.atanh())
+    }
+
+    pub fn sin_cos(&self) -> Ndarr<(T, T), R> {
+        self.map(|x| x.sin_cos())
+    }
+
+    pub fn floor(&self) -> Self {
+        self.map(|x| x.floor())
+    }
+
+    pub fn ceil(&self) -> Self {
+        self.map(|x| x.ceil())
+    }
+
+    ///Rounds to the nearest integer, half-way cases are rounded away from `0.0`.
+    pub fn round(&self) -> Self {
+        self.map(|x| x.round())
+    }
+
+    pub fn trunc(&self) -> Self {
+        self.map(|x| x.trunc())
+    }
+
+    pub fn fract(&self) -> Self {
+        self.map(|x| x.fract())
+    }
+
+    pub fn signum(&self) -> Self {
+        self.map(|x| x.signum())
+    }
+
+    pub fn clamp(&self, min: T, max: T) -> Self {
+        self.map(|x| x.clamp(min, max))
+    }
+
+    pub fn to_degrees(&self) -> Self {
+        self.map(|x| x.to_degrees())
+    }
+
+    pub fn to_radians(&self) -> Self {
+        self.map(|x| x.to_radians())
+    }
+
+    pub fn is_sign_positive(&self) -> Ndarr<bool, R> {
+        self.map(|x| x.is_sign_positive())
+    }
+
+    pub fn is_sign_negative(&self) -> Ndarr<bool, R> {
+        self.map(|x| x.is_sign_negative())
+    }
+
+    ///Four quadrant arctangent of `self` (y) and `other` (x), broadcasting both arrays to a compatible shape.
+    pub fn atan2<R2: Unsigned>(
+        &self,
+        other: &Ndarr<T, R2>,
+    ) -> Result<Ndarr<T, Maximum<R, R2>>, DimError>
+    where
+        R: Max<R2>,
+        <R as Max<R2>>::Output: Unsigned,
+    {
+        self.poly_dyadic(other, |y, x| y.atan2(x))
+    }
+
+    ///Length of the hypotenuse of a right-angle triangle with legs `self` and `other`, broadcasting both arrays to a compatible shape.
+    pub fn hypot<R2: Unsigned>(
+        &self,
+        other: &Ndarr<T, R2>,
+    ) -> Result<Ndarr<T, Maximum<R, R2>>, DimError>
+    where
+        R: Max<R2>,
+        <R as Max<R2>>::Output: Unsigned,
+    {
+        self.poly_dyadic(other, |x, y| x.hypot(y))
+    }
+
+    ///Raises each element to the power of the corresponding element of `other`, broadcasting both arrays to a compatible shape.
+    pub fn powf_arr<R2: Unsigned>(
+        &self,
+        other: &Ndarr<T, R2>,
+    ) -> Result<Ndarr<T, Maximum<R, R2>>, DimError>
+    where
+        R: Max<R2>,
+        <R as Max<R2>>::Output: Unsigned,
+    {
+        self.poly_dyadic(other, |x, n| x.powf(n))
+    }
+
+    ///Magnitude of `self` with the sign of `other`, broadcasting both arrays to a compatible shape.
+    pub fn copysign<R2: Unsigned>(
+        &self,
+        other: &Ndarr<T, R2>,
+    ) -> Result<Ndarr<T, Maximum<R, R2>>, DimError>
+    where
+        R: Max<R2>,
+        <R as Max<R2>>::Output: Unsigned,
+    {
+        self.poly_dyadic(other, |x, sign| x.copysign(sign))
+    }
+
     pub fn is_infinite(&self) -> Ndarr<bool, R> {
         self.map(|x| x.is_infinite())
     }
@@ -75,3 +245,55 @@ where
             .expect("Cannot perform minf due to imposable comparison.")
     }
 }
+
+#[cfg(test)]
+mod test_floats {
+    use super::*;
+
+    #[test]
+    fn monadic() {
+        let a = Ndarr::from([0.25, 0.5]);
+        assert_eq!(a.sqrt(), Ndarr::from([0.5, 0.5_f64.sqrt()]));
+        assert_eq!(a.asin(), Ndarr::from([0.25_f64.asin(), 0.5_f64.asin()]));
+        assert_eq!(a.log10(), Ndarr::from([0.25_f64.log10(), 0.5_f64.log10()]));
+        assert_eq!(a.powi(2), Ndarr::from([0.0625, 0.25]));
+        assert_eq!(a.powf(0.5), a.sqrt());
+        assert_eq!(
+            a.expm1(),
+            Ndarr::from([0.25_f64.exp_m1(), 0.5_f64.exp_m1()])
+        );
+        assert_eq!(a.exp_m1(), a.expm1());
+        let b = Ndarr::from([-1.5, -0.2, 0.5, 2.7]);
+        assert_eq!(b.floor(), Ndarr::from([-2., -1., 0., 2.]));
+        assert_eq!(b.ceil(), Ndarr::from([-1., -0., 1., 3.]));
+        assert_eq!(b.round(), Ndarr::from([-2., -0., 1., 3.]));
+        assert_eq!(b.trunc(), Ndarr::from([-1., -0., 0., 2.]));
+        assert_eq!(b.clamp(-1., 1.), Ndarr::from([-1., -0.2, 0.5, 1.]));
+        assert_eq!(b.signum(), Ndarr::from([-1., -1., 1., 1.]));
+    }
+
+    #[test]
+    fn dyadic() {
+        let y = Ndarr::from([[1., -1.], [0., 2.]]);
+        let x = Ndarr::from([1., 2.]);
+        assert_eq!(
+            y.atan2(&x).unwrap(),
+            Ndarr::from([[1_f64.atan2(1.), -1_f64.atan2(2.)], [0., 2_f64.atan2(2.)]])
+        );
+        assert_eq!(
+            Ndarr::from([3., 5.])
+                .hypot(&Ndarr::from([4., 12.]))
+                .unwrap(),
+            Ndarr::from([5., 13.])
+        );
+        assert_eq!(
+            x.powf_arr(&Ndarr::from([[2.], [3.]])).unwrap(),
+            Ndarr::from([[1., 4.], [1., 8.]])
+        );
+        assert_eq!(
+            x.copysign(&Ndarr::from([-1.])).unwrap(),
+            Ndarr::from([-1., -2.])
+        );
+        assert!(x.hypot(&Ndarr::from([1., 2., 3.])).is_err());
+    }
+}
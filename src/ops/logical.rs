@@ -0,0 +1,71 @@
+use super::*;
+
+impl<R: Unsigned> Ndarr<bool, R> {
+    ///Reduces along the specified axis, returning `true` where all the elements of the axis are `true`.
+    pub fn all(&self, axis: usize) -> Result<Ndarr<bool, Sub1<R>>, DimError>
+    where
+        R: Sub<B1>,
+        <R as Sub<B1>>::Output: Unsigned,
+    {
+        self.reduce_or(axis, true, |x, y| x && y)
+    }
+
+    ///Reduces along the specified axis, returning `true` where any of the elements of the axis is `true`.
+    pub fn any(&self, axis: usize) -> Result<Ndarr<bool, Sub1<R>>, DimError>
+    where
+        R: Sub<B1>,
+        <R as Sub<B1>>::Output: Unsigned,
+    {
+        self.reduce_or(axis, false, |x, y| x || y)
+    }
+
+    //`reduce` that returns `identity` everywhere when the axis is empty.
+    fn reduce_or<F: Fn(bool, bool) -> bool + Clone>(
+        &self,
+        axis: usize,
+        identity: bool,
+        f: F,
+    ) -> Result<Ndarr<bool, Sub1<R>>, DimError>
+    where
+        R: Sub<B1>,
+        <R as Sub<B1>>::Output: Unsigned,
+    {
+        if axis < R::to_usize() && self.shape()[axis] == 0 {
+            let dim = self.dim.clone().remove_element(axis);
+            return Ok(Ndarr::fill(identity, dim));
+        }
+        self.reduce(axis, f)
+    }
+}
+
+impl<T: Clone + Debug + Default + PartialEq, R: Unsigned> Ndarr<T, R> {
+    ///Counts the number of elements that are different from `T::default()`, i.e. non zero numbers or `true` booleans.
+    pub fn count_nonzero(&self) -> usize {
+        let zero = T::default();
+        self.data.iter().filter(|x| **x != zero).count()
+    }
+}
+
+#[cfg(test)]
+mod test_logical {
+    use super::*;
+
+    #[test]
+    fn all_any() {
+        let a = Ndarr::from([[true, false], [true, true]]);
+        assert_eq!(a.all(0).unwrap(), Ndarr::from([true, false]));
+        assert_eq!(a.all(1).unwrap(), Ndarr::from([false, true]));
+        assert_eq!(a.any(0).unwrap(), Ndarr::from([true, true]));
+        assert!(!Ndarr::from([false, false]).any(0).unwrap().scalar());
+        let empty: Ndarr<bool, U2> = Ndarr::fill(true, [2, 0]);
+        assert_eq!(empty.all(1).unwrap(), Ndarr::from([true, true]));
+        assert_eq!(empty.any(1).unwrap(), Ndarr::from([false, false]));
+        assert_eq!(empty.all(0).unwrap().shape(), &[0]);
+    }
+
+    #[test]
+    fn count_nonzero() {
+        assert_eq!(Ndarr::from([[0, 1], [2, 0]]).count_nonzero(), 2);
+        assert_eq!(Ndarr::from([true, false, true]).count_nonzero(), 2);
+    }
+}
@@ -34,6 +34,53 @@ impl<T1: Clone, R: Unsigned> Ndarr<T1, R> {
         }
     }
 
+    //Applies `f` to every lane of the array along `axis`, i.e. the 1D sequences obtained by fixing all the other indexes.
+    //Every call to `f` must return `new_len` elements, which replace the lane in the result, otherwise a `DimError` is returned.
+    pub(crate) fn map_lanes<T2: Clone, F: FnMut(Vec<T1>) -> Vec<T2>>(
+        &self,
+        axis: usize,
+        new_len: usize,
+        mut f: F,
+    ) -> Result<Ndarr<T2, R>, DimError> {
+        if axis >= self.rank() {
+            return Err(DimError::new("Axis grater than rank"));
+        }
+        let shape = self.shape();
+        let len = shape[axis];
+        let outer = crate::helpers::multiply_list(&shape[..axis], 1);
+        let inner = crate::helpers::multiply_list(&shape[axis + 1..], 1);
+        let mut lanes = Vec::with_capacity(outer * inner);
+        for o in 0..outer {
+            for i in 0..inner {
+                let lane = (0..len)
+                    .map(|k| self.data[(o * len + k) * inner + i].clone())
+                    .collect();
+                let mapped = f(lane);
+                if mapped.len() != new_len {
+                    return Err(DimError::new(&format!(
+                        "Lane mapped to {} elements, expected {}",
+                        mapped.len(),
+                        new_len
+                    )));
+                }
+                lanes.push(mapped.into_iter());
+            }
+        }
+        //every lane yields exactly new_len elements, take the k-th one of each lane in turn
+        let mut data = Vec::with_capacity(outer * new_len * inner);
+        for block in lanes.chunks_mut(inner.max(1)) {
+            for _ in 0..new_len {
+                data.extend(block.iter_mut().filter_map(Iterator::next));
+            }
+        }
+        let mut new_shape = shape.to_vec();
+        new_shape[axis] = new_len;
+        Ok(Ndarr {
+            data,
+            dim: Dim::new(&new_shape)?,
+        })
+    }
+
     pub fn scanr<F: Fn(T1, T1) -> T1>(&self, axis: usize, f: F) -> Self
     where
         T1: Default,
@@ -42,6 +89,9 @@ impl<T1: Clone, R: Unsigned> Ndarr<T1, R> {
         <R as Sub<B1>>::Output: Add<B1>,
         <<R as Sub<B1>>::Output as Add<B1>>::Output: Unsigned,
     {
+        if self.is_empty() {
+            return self.clone();
+        }
         let mut slices = self.slice_at(axis);
         for i in 0..slices.len() - 1 {
             slices[i + 1] = slices[i + 1].bimap(&slices[i], &f)
@@ -61,6 +111,9 @@ impl<T1: Clone, R: Unsigned> Ndarr<T1, R> {
         <R as Sub<B1>>::Output: Add<B1>,
         <<R as Sub<B1>>::Output as Add<B1>>::Output: Unsigned,
     {
+        if self.is_empty() {
+            return self.clone();
+        }
         let mut slices = self.slice_at(axis);
         let l = slices.len();
         for i in 0..slices.len() - 1 {
@@ -73,3 +126,24 @@ impl<T1: Clone, R: Unsigned> Ndarr<T1, R> {
         }
     }
 }
+
+#[cfg(test)]
+mod maps_test {
+    use super::*;
+
+    #[test]
+    fn map_lanes() {
+        let a = Ndarr::from(0..6).reshape([2, 3]).unwrap();
+        let rev = a.map_lanes(0, 2, |mut l| {
+            l.reverse();
+            l
+        });
+        assert_eq!(
+            rev.unwrap(),
+            Ndarr::from([3, 4, 5, 0, 1, 2]).reshape([2, 3]).unwrap()
+        );
+        let sums = a.map_lanes(1, 1, |l| vec![l.iter().sum::<i32>()]);
+        assert_eq!(sums.unwrap(), Ndarr::from([3, 12]).reshape([2, 1]).unwrap());
+        assert!(a.map_lanes(1, 2, |l| l).is_err());
+    }
+}
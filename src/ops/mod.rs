@@ -2,9 +2,21 @@ pub use super::Ndarr;
 use super::*;
 
 mod arithmetics;
+mod cast;
+mod contraction;
+mod cumulative;
 mod dyadic;
 mod extras;
 mod floats;
+mod logical;
 mod maps;
+mod products;
+mod rows;
+mod search;
+mod sets;
 
+pub use contraction::{einsum, tensordot};
 pub use dyadic::*;
+pub use products::{khatri_rao, kron, outer_n};
+pub use search::Side;
+pub use sets::group_by;
@@ -0,0 +1,158 @@
+use super::*;
+use std::ops::Mul;
+use typenum::{Max, Maximum};
+
+use crate::helpers::multiply_list;
+
+///Kronecker product of two arrays. If the ranks are different the shape of the smaller one is padded with ones on the left,
+///the resulting shape is the element wise product of both shapes.
+///```
+///use rapl::*;
+///let a = Ndarr::from([[1, 2], [3, 4]]);
+///let b = Ndarr::from([[0, 1], [1, 0]]);
+///let k = ops::kron(&a, &b);
+///assert_eq!(k, Ndarr::from([[0, 1, 0, 2], [1, 0, 2, 0], [0, 3, 0, 4], [3, 0, 4, 0]]));
+///```
+pub fn kron<T, R1, R2>(a: &Ndarr<T, R1>, b: &Ndarr<T, R2>) -> Ndarr<T, Maximum<R1, R2>>
+where
+    T: Clone + Debug + Mul<Output = T>,
+    R1: Unsigned + Max<R2>,
+    R2: Unsigned,
+    <R1 as Max<R2>>::Output: Unsigned,
+{
+    let r = a.rank().max(b.rank());
+    let sa = a.dim.path_shape::<UTerm>(r).unwrap();
+    let sb = b.dim.path_shape::<UTerm>(r).unwrap();
+    let shape: Vec<usize> = sa.shape.iter().zip(&sb.shape).map(|(x, y)| x * y).collect();
+    let out_dim = Dim::<UTerm>::new(&shape).unwrap();
+    let n = multiply_list(&shape, 1);
+    let mut data = Vec::with_capacity(n);
+    let mut ia = vec![0; r];
+    let mut ib = vec![0; r];
+    for i in 0..n {
+        let idx = out_dim.get_indexes(&i).shape;
+        for d in 0..r {
+            ia[d] = idx[d] / sb.shape[d];
+            ib[d] = idx[d] % sb.shape[d];
+        }
+        let x = a.data[sa.get_flat_pos(&Dim::<UTerm>::new(&ia).unwrap()).unwrap()].clone();
+        let y = b.data[sb.get_flat_pos(&Dim::<UTerm>::new(&ib).unwrap()).unwrap()].clone();
+        data.push(x * y);
+    }
+    Ndarr {
+        data,
+        dim: Dim::new(&shape).unwrap(),
+    }
+}
+
+///Khatri-Rao product, the column wise Kronecker product of two matrices with the same number of columns.
+///For matrices of shape `[m, n]` and `[p, n]` the result has shape `[m * p, n]`.
+pub fn khatri_rao<T>(a: &Ndarr<T, U2>, b: &Ndarr<T, U2>) -> Result<Ndarr<T, U2>, DimError>
+where
+    T: Clone + Debug + Mul<Output = T>,
+{
+    let (m, n) = (a.shape()[0], a.shape()[1]);
+    let (p, n2) = (b.shape()[0], b.shape()[1]);
+    if n != n2 {
+        return Err(DimError::new(&format!(
+            "Can not compute the Khatri-Rao product of matrices of shape {:?} and {:?}.",
+            a.shape(),
+            b.shape()
+        )));
+    }
+    let mut data = Vec::with_capacity(m * p * n);
+    for i in 0..m {
+        for k in 0..p {
+            for j in 0..n {
+                data.push(a.data[i * n + j].clone() * b.data[k * n + j].clone());
+            }
+        }
+    }
+    Ndarr::new(&data, [m * p, n])
+}
+
+///Generalized outer product of many arrays. Applies `f` to every combination of elements, one from each array,
+///the resulting shape is the concatenation of the shapes of all the arrays.
+///```
+///use rapl::*;
+///let x = Ndarr::from([0, 1]).into_notyped();
+///let y = Ndarr::from([10, 20]).into_notyped();
+///let z = Ndarr::from([100, 200, 300]).into_notyped();
+///let grid = ops::outer_n(&[x, y, z], |v| v.iter().sum::<i32>());
+///assert_eq!(grid.shape(), &[2, 2, 3]);
+///assert_eq!(grid.data[..3], [110, 210, 310]);
+///```
+pub fn outer_n<T, U, F>(arrays: &[Ndarr<T, UTerm>], f: F) -> Ndarr<U, UTerm>
+where
+    T: Clone + Debug,
+    U: Clone + Debug,
+    F: Fn(&[T]) -> U,
+{
+    let shape: Vec<usize> = arrays.iter().flat_map(|a| a.shape().to_vec()).collect();
+    let sizes: Vec<usize> = arrays.iter().map(|a| a.len()).collect();
+    let n = multiply_list(&sizes, 1);
+    let mut data = Vec::with_capacity(n);
+    if n > 0 {
+        let mut idx = vec![0; arrays.len()];
+        let mut args: Vec<T> = arrays.iter().map(|a| a.data[0].clone()).collect();
+        for _ in 0..n {
+            data.push(f(&args));
+            //advance the index of the last array and carry to the left
+            for k in (0..arrays.len()).rev() {
+                idx[k] = (idx[k] + 1) % sizes[k];
+                args[k] = arrays[k].data[idx[k]].clone();
+                if idx[k] != 0 {
+                    break;
+                }
+            }
+        }
+    }
+    Ndarr {
+        data,
+        dim: Dim::new(&shape).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod products_test {
+    use super::*;
+
+    #[test]
+    fn kron_nd() {
+        let a = Ndarr::from([1, 2]);
+        let b = Ndarr::from([[1, 10], [100, 1000]]);
+        assert_eq!(
+            kron(&a, &b),
+            Ndarr::from([[1, 10, 2, 20], [100, 1000, 200, 2000]])
+        );
+        let id = Ndarr::from([[1, 0], [0, 1]]);
+        let x = Ndarr::from([[0, 1], [1, 0]]);
+        let ix = kron(&id, &x);
+        assert_eq!(ix.shape(), &[4, 4]);
+        assert_eq!(ix[[2, 3]], 1);
+        assert_eq!(ix[[0, 3]], 0);
+    }
+
+    #[test]
+    fn khatri_rao_columns() {
+        let a = Ndarr::from([[1, 2], [3, 4]]);
+        let b = Ndarr::from([[1, 1], [2, 3]]);
+        assert_eq!(
+            khatri_rao(&a, &b).unwrap(),
+            Ndarr::from([[1, 2], [2, 6], [3, 4], [6, 12]])
+        );
+        assert!(khatri_rao(&a, &Ndarr::from([[1, 2, 3]])).is_err());
+    }
+
+    #[test]
+    fn outer_many() {
+        let suits = Ndarr::from(["a", "b"]).into_notyped();
+        let ranks = Ndarr::from(["1", "2", "3"]).into_notyped();
+        let deck = outer_n(&[ranks, suits], |v| v.concat());
+        assert_eq!(deck.data, vec!["1a", "1b", "2a", "2b", "3a", "3b"]);
+        let m = Ndarr::from([[1, 2], [3, 4]]).into_notyped();
+        let v = Ndarr::from([1, -1]).into_notyped();
+        let o = outer_n(&[m, v], |x| x[0] * x[1]);
+        assert_eq!(o.into_rank::<U3>().unwrap()[[1, 0, 1]], -3);
+    }
+}
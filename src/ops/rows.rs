@@ -0,0 +1,145 @@
+use super::*;
+use crate::helpers::nan_last_cmp;
+use std::cmp::Ordering;
+use std::ops::Sub;
+use typenum::{Sub1, B1};
+
+impl<T: Clone + Debug + PartialOrd, R: Unsigned> Ndarr<T, R> {
+    //Data of the major cells, i.e. the slices along the first axis.
+    fn major_cells(&self) -> Vec<&[T]> {
+        let n = self.shape().first().copied().unwrap_or(0);
+        let cell = self.len().checked_div(n).unwrap_or(0);
+        (0..n)
+            .map(|i| &self.data[i * cell..(i + 1) * cell])
+            .collect()
+    }
+
+    //Builds an array with the major cells in the given order.
+    fn select_cells(&self, order: &[usize]) -> Self {
+        let cells = self.major_cells();
+        let mut shape = self.shape().to_vec();
+        if let Some(n) = shape.first_mut() {
+            *n = order.len();
+        }
+        Ndarr {
+            data: order.iter().flat_map(|i| cells[*i].to_vec()).collect(),
+            dim: Dim::new(&shape).unwrap(),
+        }
+    }
+
+    ///Permutation of indexes that sorts the rows, or major cells for arrays of any rank, in lexicographic order.
+    ///Equal rows keep their relative order and NaN sorts after every other value.
+    pub fn argsort_rows(&self) -> Ndarr<usize, U1> {
+        let cells = self.major_cells();
+        let mut order: Vec<usize> = (0..cells.len()).collect();
+        order.sort_by(|a, b| {
+            cells[*a]
+                .iter()
+                .zip(cells[*b])
+                .map(|(x, y)| nan_last_cmp(x, y))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        Ndarr::from(order)
+    }
+
+    ///Sorts the rows, or major cells for arrays of any rank, in lexicographic order.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[2, 1], [1, 5], [2, 0]]);
+    ///assert_eq!(a.sort_rows(), Ndarr::from([[1, 5], [2, 0], [2, 1]]));
+    ///```
+    pub fn sort_rows(&self) -> Self {
+        self.select_cells(&self.argsort_rows().data)
+    }
+
+    ///Unique rows, or major cells for arrays of any rank, in lexicographic order.
+    pub fn unique_rows(&self) -> Self {
+        let cells = self.major_cells();
+        let mut order = self.argsort_rows().data;
+        order.dedup_by(|a, b| cells[*a] == cells[*b]);
+        self.select_cells(&order)
+    }
+
+    ///Returns a boolean array that is `true` for every row, or major cell, equal to `row`.
+    pub fn match_rows(&self, row: &Ndarr<T, Sub1<R>>) -> Ndarr<bool, U1>
+    where
+        R: Sub<B1>,
+        <R as Sub<B1>>::Output: Unsigned,
+    {
+        let cells = self.major_cells();
+        let same_shape = self.shape()[1..] == *row.shape();
+        Ndarr::from(
+            cells
+                .iter()
+                .map(|c| same_shape && *c == row.data.as_slice())
+                .collect::<Vec<bool>>(),
+        )
+    }
+
+    ///Index of the first row, or major cell, equal to `row`.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1, 2], [3, 4], [1, 2]]);
+    ///assert_eq!(a.find_row(&Ndarr::from([1, 2])), Some(0));
+    ///assert_eq!(a.find_row(&Ndarr::from([4, 3])), None);
+    ///```
+    pub fn find_row(&self, row: &Ndarr<T, Sub1<R>>) -> Option<usize>
+    where
+        R: Sub<B1>,
+        <R as Sub<B1>>::Output: Unsigned,
+    {
+        self.match_rows(row).data.iter().position(|b| *b)
+    }
+}
+
+#[cfg(test)]
+mod rows_test {
+    use super::*;
+
+    #[test]
+    fn sort_rows() {
+        let a = Ndarr::from([[1., -1., 1.], [-1., 1., 1.], [1., -1., -1.], [1., -1., 1.]]);
+        assert_eq!(a.argsort_rows(), Ndarr::from([1, 2, 0, 3]));
+        assert_eq!(
+            a.unique_rows(),
+            Ndarr::from([[-1., 1., 1.], [1., -1., -1.], [1., -1., 1.]])
+        );
+        let v = Ndarr::from([3, 1, 2]);
+        assert_eq!(v.argsort_rows(), v.grade_up());
+        assert_eq!(v.sort_rows(), Ndarr::from([1, 2, 3]));
+    }
+
+    #[test]
+    fn sort_rows_nan() {
+        let mut data: Vec<f64> = (0..128).map(|i| ((i * 37) % 16) as f64).collect();
+        data[10] = f64::NAN;
+        data[51] = f64::NAN;
+        data[52] = f64::NAN;
+        let a = Ndarr::from(data).reshape([64, 2]).unwrap();
+        assert_eq!(a.argsort_rows().data[62..], [5, 26]);
+        //rows sharing the first column are ordered by the second one, with NaN last
+        let sorted = a.sort_rows();
+        let first: Vec<f64> = sorted.data[..124].iter().step_by(2).copied().collect();
+        assert!(first.windows(2).all(|w| w[0] <= w[1]));
+        let tens: Vec<f64> = sorted.data[..124]
+            .chunks(2)
+            .filter(|r| r[0] == 10.)
+            .map(|r| r[1])
+            .collect();
+        assert!(tens.last().unwrap().is_nan());
+        //8 distinct rows repeat along the array, rows with NaN are never equal to each other
+        assert_eq!(a.unique_rows().shape()[0], 11);
+    }
+
+    #[test]
+    fn major_cells() {
+        let a = Ndarr::from([[[1, 1], [0, 0]], [[0, 1], [0, 0]], [[1, 1], [0, 0]]]);
+        assert_eq!(a.unique_rows().shape(), &[2, 2, 2]);
+        assert_eq!(a.sort_rows().slice_at(0)[0], Ndarr::from([[0, 1], [0, 0]]));
+        let m = a.match_rows(&Ndarr::from([[1, 1], [0, 0]]));
+        assert_eq!(m, Ndarr::from([true, false, true]));
+        assert_eq!(a.find_row(&Ndarr::from([[0, 1], [0, 0]])), Some(1));
+        assert_eq!(a.find_row(&Ndarr::from([[0, 1]])), None);
+    }
+}
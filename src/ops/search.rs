@@ -0,0 +1,180 @@
+use super::*;
+use crate::helpers::nan_last_cmp;
+
+///Side of the insertion point used by `searchsorted` when the value is already in the array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    ///The index of the first element equal to the value.
+    Left,
+    ///The index after the last element equal to the value.
+    Right,
+}
+
+impl<T: Clone + Debug + PartialOrd> Ndarr<T, U1> {
+    ///Finds the indexes where the elements of `values` should be inserted to keep the sorted array `self` in order.
+    ///```
+    ///use rapl::*;
+    ///use rapl::ops::Side;
+    ///let a = Ndarr::from([1, 2, 2, 3]);
+    ///assert_eq!(a.searchsorted(&Ndarr::from([2, 4]), Side::Left), Ndarr::from([1, 4]));
+    ///assert_eq!(a.searchsorted(&Ndarr::from([2, 0]), Side::Right), Ndarr::from([3, 0]));
+    ///```
+    pub fn searchsorted<R2: Unsigned>(
+        &self,
+        values: &Ndarr<T, R2>,
+        side: Side,
+    ) -> Ndarr<usize, R2> {
+        values.map(|v| match side {
+            Side::Left => self.data.partition_point(|x| x < v),
+            Side::Right => self.data.partition_point(|x| x <= v),
+        })
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + Default, R: Unsigned> Ndarr<T, R> {
+    //Flat positions of the elements different from `T::default()`.
+    fn nonzero_flat(&self) -> Vec<usize> {
+        let zero = T::default();
+        (0..self.len()).filter(|i| self.data[*i] != zero).collect()
+    }
+
+    ///Indexes of the elements different from `T::default()`, one array for each axis.
+    pub fn nonzero(&self) -> Vec<Ndarr<usize, U1>> {
+        let flat = self.nonzero_flat();
+        let indexes: Vec<Vec<usize>> = flat.iter().map(|i| self.dim.get_indexes(i).shape).collect();
+        (0..self.rank())
+            .map(|axis| Ndarr::from(indexes.iter().map(|ind| ind[axis]).collect::<Vec<usize>>()))
+            .collect()
+    }
+
+    ///Indexes of the elements different from `T::default()`, as an array of shape `[n, rank]` with one index per row.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[0, 1], [2, 0]]);
+    ///assert_eq!(a.argwhere(), Ndarr::from([[0, 1], [1, 0]]));
+    ///```
+    pub fn argwhere(&self) -> Ndarr<usize, U2> {
+        let flat = self.nonzero_flat();
+        let data: Vec<usize> = flat
+            .iter()
+            .flat_map(|i| self.dim.get_indexes(i).shape)
+            .collect();
+        Ndarr {
+            data,
+            dim: Dim::new(&[flat.len(), self.rank()]).unwrap(),
+        }
+    }
+}
+
+impl<T: Clone + Debug + PartialOrd, R: Unsigned> Ndarr<T, R> {
+    ///The `k` largest elements along an axis in descending order, together with their indexes along that axis.
+    ///NaN is considered larger than any other value.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1, 5, 3], [4, 2, 6]]);
+    ///let (values, indexes) = a.top_k(2, 1).unwrap();
+    ///assert_eq!(values, Ndarr::from([[5, 3], [6, 4]]));
+    ///assert_eq!(indexes, Ndarr::from([[1, 2], [2, 0]]));
+    ///```
+    pub fn top_k(&self, k: usize, axis: usize) -> Result<(Self, Ndarr<usize, R>), DimError> {
+        let len = *self.shape().get(axis).unwrap_or(&0);
+        if k > len {
+            return Err(DimError::new(&format!(
+                "Can not take the top {} elements of an axis of length {}.",
+                k, len
+            )));
+        }
+        let sorted = self.map_lanes(axis, k, |lane| {
+            let mut pairs: Vec<(usize, T)> = lane.into_iter().enumerate().collect();
+            pairs.sort_by(|a, b| nan_last_cmp(&b.1, &a.1));
+            pairs.truncate(k);
+            pairs
+        })?;
+        Ok((sorted.map(|p| p.1.clone()), sorted.map(|p| p.0)))
+    }
+
+    ///Rearranges the elements along an axis so the element at position `kth` is the one that would be there if the axis were sorted,
+    ///all the elements before it are smaller or equal and all the elements after it are greater or equal.
+    ///The order inside both partitions is unspecified, NaN is sorted last.
+    pub fn partition(&self, kth: usize, axis: usize) -> Result<Self, DimError> {
+        let len = *self.shape().get(axis).unwrap_or(&0);
+        if kth >= len {
+            return Err(DimError::new(&format!(
+                "Partition index {} out of bounds for axis of length {}.",
+                kth, len
+            )));
+        }
+        self.map_lanes(axis, len, |mut lane| {
+            lane.select_nth_unstable_by(kth, nan_last_cmp);
+            lane
+        })
+    }
+}
+
+#[cfg(test)]
+mod search_test {
+    use super::*;
+
+    #[test]
+    fn searchsorted() {
+        let a = Ndarr::from([0., 0.5, 1., 1., 2.]);
+        let v = Ndarr::from([[-1., 1.], [1.5, 3.]]);
+        assert_eq!(
+            a.searchsorted(&v, Side::Left),
+            Ndarr::from([[0, 2], [4, 5]])
+        );
+        assert_eq!(
+            a.searchsorted(&v, Side::Right),
+            Ndarr::from([[0, 4], [4, 5]])
+        );
+    }
+
+    #[test]
+    fn nonzero() {
+        let a = Ndarr::from([[0, 3, 0], [1, 0, 2]]);
+        let nz = a.nonzero();
+        assert_eq!(nz[0], Ndarr::from([0, 1, 1]));
+        assert_eq!(nz[1], Ndarr::from([1, 0, 2]));
+        assert_eq!(a.argwhere(), Ndarr::from([[0, 1], [1, 0], [1, 2]]));
+        let b = Ndarr::from([false, false]);
+        assert_eq!(b.argwhere().shape(), &[0, 1]);
+    }
+
+    #[test]
+    fn top_k() {
+        let a = Ndarr::from([[1, 5], [4, 2], [3, 6]]);
+        let (values, indexes) = a.top_k(1, 0).unwrap();
+        assert_eq!(values, Ndarr::from([[4, 6]]));
+        assert_eq!(indexes, Ndarr::from([[1, 2]]));
+        assert!(a.top_k(4, 0).is_err());
+        assert!(a.top_k(1, 2).is_err());
+    }
+
+    #[test]
+    fn partition() {
+        let a = Ndarr::from([[7, 1, 5, 3, 9], [2, 8, 6, 4, 0]]);
+        let p = a.partition(2, 1).unwrap();
+        for (row, kth) in p.slice_at(0).iter().zip([5, 4]) {
+            assert_eq!(row.data[2], kth);
+            assert!(row.data[..2].iter().all(|x| *x <= kth));
+            assert!(row.data[3..].iter().all(|x| *x >= kth));
+        }
+        assert!(a.partition(5, 1).is_err());
+    }
+
+    #[test]
+    fn nan() {
+        let mut data: Vec<f64> = (0..64).map(|i| ((i * 37) % 64) as f64).collect();
+        data[7] = f64::NAN;
+        let a = Ndarr::from(data);
+        let (values, indexes) = a.top_k(5, 0).unwrap();
+        assert!(values.data[0].is_nan());
+        assert_eq!(values.data[1..], [63., 62., 61., 60.]);
+        assert_eq!(indexes.data[0], 7);
+        let p = a.partition(63, 0).unwrap();
+        assert!(p.data[63].is_nan());
+        let p = a.partition(10, 0).unwrap();
+        assert!(p.data[..10].iter().all(|x| *x <= p.data[10]));
+        assert!(p.data[11..].iter().all(|x| x.is_nan() || *x >= p.data[10]));
+    }
+}
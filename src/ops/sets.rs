@@ -0,0 +1,217 @@
+use super::*;
+use num_traits::One;
+use std::collections::BTreeMap;
+use std::ops::Add;
+
+use crate::helpers::multiply_list;
+
+//Unique keys and their reduced values.
+type Groups<K, V, R> = (Ndarr<K, U1>, Ndarr<V, R>);
+
+//Wraps a vector into a rank one array, for element types that are not `Scalar`.
+fn vec_to_ndarr<T: Clone>(data: Vec<T>) -> Ndarr<T, U1> {
+    Ndarr {
+        dim: Dim::new(&[data.len()]).unwrap(),
+        data,
+    }
+}
+
+impl<T: Clone + Debug + Ord> Ndarr<T, U1> {
+    ///Sorted unique elements of the array.
+    pub fn unique(&self) -> Self {
+        let mut data = self.data.clone();
+        data.sort();
+        data.dedup();
+        vec_to_ndarr(data)
+    }
+
+    ///Sorted unique elements of the array and the number of times each one appears.
+    pub fn unique_counts(&self) -> (Self, Ndarr<usize, U1>) {
+        let mut counts: BTreeMap<T, usize> = BTreeMap::new();
+        for x in self.data.iter() {
+            *counts.entry(x.clone()).or_insert(0) += 1;
+        }
+        let (uniques, counts): (Vec<T>, Vec<usize>) = counts.into_iter().unzip();
+        (vec_to_ndarr(uniques), vec_to_ndarr(counts))
+    }
+
+    ///Sorted unique elements of the array and the indexes of the unique array that reconstruct the original one,
+    ///i.e. `unique[inverse[i]] == self[i]`.
+    pub fn unique_inverse(&self) -> (Self, Ndarr<usize, U1>) {
+        let unique = self.unique();
+        let inverse = self.map(|x| unique.data.binary_search(x).unwrap());
+        (unique, inverse)
+    }
+
+    ///Sorted unique elements that are in either of the arrays.
+    pub fn union1d(&self, other: &Self) -> Self {
+        let mut data = self.data.clone();
+        data.extend(other.data.iter().cloned());
+        vec_to_ndarr(data).unique()
+    }
+
+    ///Sorted unique elements that are in both arrays.
+    pub fn intersect1d(&self, other: &Self) -> Self {
+        let other = other.unique();
+        let data: Vec<T> = self
+            .unique()
+            .data
+            .into_iter()
+            .filter(|x| other.data.binary_search(x).is_ok())
+            .collect();
+        vec_to_ndarr(data)
+    }
+
+    ///Sorted unique elements of `self` that are not in `other`.
+    pub fn setdiff1d(&self, other: &Self) -> Self {
+        let other = other.unique();
+        let data: Vec<T> = self
+            .unique()
+            .data
+            .into_iter()
+            .filter(|x| other.data.binary_search(x).is_err())
+            .collect();
+        vec_to_ndarr(data)
+    }
+}
+
+impl Ndarr<usize, U1> {
+    ///Counts the occurrences of each value `0..=max`. If `weights` are given the weight of each element is added instead of one.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([0, 1, 1, 3]);
+    ///assert_eq!(a.bincount::<usize>(None).unwrap(), Ndarr::from([1, 2, 0, 1]));
+    ///let w = Ndarr::from([0.5, 1., 1., 2.]);
+    ///assert_eq!(a.bincount(Some(&w)).unwrap(), Ndarr::from([0.5, 2., 0., 2.]));
+    ///```
+    pub fn bincount<W>(&self, weights: Option<&Ndarr<W, U1>>) -> Result<Ndarr<W, U1>, DimError>
+    where
+        W: Clone + Debug + Default + One + Add<Output = W>,
+    {
+        if let Some(w) = weights {
+            if w.len() != self.len() {
+                return Err(DimError::new(&format!(
+                    "Weights of length {} do not match array of length {}.",
+                    w.len(),
+                    self.len()
+                )));
+            }
+        }
+        let n = self.data.iter().max().map_or(0, |m| m + 1);
+        let mut data = vec![W::default(); n];
+        for (i, x) in self.data.iter().enumerate() {
+            let w = match weights {
+                Some(w) => w.data[i].clone(),
+                None => W::one(),
+            };
+            data[*x] = data[*x].clone() + w;
+        }
+        Ok(vec_to_ndarr(data))
+    }
+}
+
+///APL key (`⌸`), groups the major cells of `values` by the corresponding element of `keys` and reduces every group with `f`.
+///Returns the sorted unique keys and an array with one reduced cell per key.
+///```
+///use rapl::*;
+///let keys = Ndarr::from(["b", "a", "b", "a", "c"]);
+///let values = Ndarr::from([1, 2, 3, 4, 5]);
+///let (k, sums) = ops::group_by(&keys, &values, |x, y| x + y).unwrap();
+///assert_eq!(k, Ndarr::from(["a", "b", "c"]));
+///assert_eq!(sums, Ndarr::from([6, 4, 5]));
+///```
+pub fn group_by<K, V, R, F>(
+    keys: &Ndarr<K, U1>,
+    values: &Ndarr<V, R>,
+    f: F,
+) -> Result<Groups<K, V, R>, DimError>
+where
+    K: Clone + Debug + Ord,
+    V: Clone + Debug,
+    R: Unsigned,
+    F: Fn(V, V) -> V,
+{
+    if values.rank() == 0 || values.shape()[0] != keys.len() {
+        return Err(DimError::new(&format!(
+            "Can not group array of shape {:?} with {} keys.",
+            values.shape(),
+            keys.len()
+        )));
+    }
+    let cell = multiply_list(&values.shape()[1..], 1);
+    let mut groups: BTreeMap<K, Vec<V>> = BTreeMap::new();
+    for (i, k) in keys.data.iter().enumerate() {
+        let cell_data = &values.data[i * cell..(i + 1) * cell];
+        match groups.get_mut(k) {
+            Some(acc) => {
+                for (a, x) in acc.iter_mut().zip(cell_data) {
+                    *a = f(a.clone(), x.clone());
+                }
+            }
+            None => {
+                groups.insert(k.clone(), cell_data.to_vec());
+            }
+        }
+    }
+    let mut shape = values.shape().to_vec();
+    shape[0] = groups.len();
+    let mut unique = Vec::with_capacity(groups.len());
+    let mut data = Vec::with_capacity(groups.len() * cell);
+    for (k, v) in groups {
+        unique.push(k);
+        data.extend(v);
+    }
+    Ok((
+        vec_to_ndarr(unique),
+        Ndarr {
+            data,
+            dim: Dim::new(&shape)?,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod sets_test {
+    use super::*;
+
+    #[test]
+    fn unique() {
+        let a = Ndarr::from([3, 1, 2, 3, 1, 3]);
+        assert_eq!(a.unique(), Ndarr::from([1, 2, 3]));
+        let (u, c) = a.unique_counts();
+        assert_eq!(u, Ndarr::from([1, 2, 3]));
+        assert_eq!(c, Ndarr::from([2, 1, 3]));
+        let (u, inv) = a.unique_inverse();
+        assert_eq!(inv, Ndarr::from([2, 0, 1, 2, 0, 2]));
+        assert_eq!(inv.map(|i| u.data[*i]), a);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = Ndarr::from([5, 1, 3, 1]);
+        let b = Ndarr::from([3, 4, 5, 6]);
+        assert_eq!(a.union1d(&b), Ndarr::from([1, 3, 4, 5, 6]));
+        assert_eq!(a.intersect1d(&b), Ndarr::from([3, 5]));
+        assert_eq!(a.setdiff1d(&b), Ndarr::from([1]));
+        assert_eq!(b.setdiff1d(&a), Ndarr::from([4, 6]));
+    }
+
+    #[test]
+    fn bincount() {
+        let a = Ndarr::from([2, 2, 0]);
+        assert_eq!(a.bincount::<i32>(None).unwrap(), Ndarr::from([1, 0, 2]));
+        assert!(a.bincount(Some(&Ndarr::from([1, 2]))).is_err());
+        let empty: Ndarr<usize, U1> = Ndarr::from(Vec::new());
+        assert!(empty.bincount::<usize>(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn group_by_cells() {
+        let keys = Ndarr::from([1, 0, 1]);
+        let values = Ndarr::from([[1, 2], [3, 4], [5, 6]]);
+        let (k, maxs) = group_by(&keys, &values, |x: i32, y| x.max(y)).unwrap();
+        assert_eq!(k, Ndarr::from([0, 1]));
+        assert_eq!(maxs, Ndarr::from([[3, 4], [5, 6]]));
+        assert!(group_by(&Ndarr::from([0]), &values, |x, y| x + y).is_err());
+    }
+}
@@ -0,0 +1,475 @@
+use num_traits::NumCast;
+use std::fmt::Debug;
+
+#[cfg(feature = "complex")]
+use crate::complex::C;
+#[cfg(feature = "complex")]
+use num_traits::Num;
+
+///Type promotion between the element types of two `Ndarr`s, similar to NumPy's `result_type`.
+///Mixed operands are converted to a common type before performing an arithmetic operation, e.g. `i32` and `f64` promote to `f64`,
+///signed and unsigned integers promote to a signed integer wide enough to hold both, and any real type together with a complex type promotes to a complex type.
+///
+///Operators between two `Ndarr`s are only defined for element types that promote to each other, so pairs whose operator is
+///defined between two different types without a common one (e.g. `String + &str`) are no longer covered by `+`, use `poly_dyadic` instead:
+///```
+///use rapl::*;
+///let a = Ndarr::from(["a", "b"]).map(|s| s.to_string());
+///let b = Ndarr::from(["x", "y"]);
+///let c = a.poly_dyadic(&b, |x, y| x + y).unwrap();
+///assert_eq!(c, Ndarr::from(["ax", "by"]).map(|s| s.to_string()));
+///```
+pub trait Promote<Rhs> {
+    type Output: Clone + Debug + Default;
+    fn promote(self, rhs: Rhs) -> (Self::Output, Self::Output);
+}
+
+///Type promotion between the element type of an `Ndarr` and a scalar. Scalars are "weak" as in NumPy (NEP 50):
+///the array type is preserved unless the scalar is of a higher kind (integer < float < complex), e.g. `Ndarr<f32, R> * 2.0` stays `f32` and `Ndarr<u8, R> * 0.5f32` becomes `f32`.
+///A scalar that does not fit the preserved type panics rather than wrapping, e.g. `Ndarr<u8, R> + 300`.
+pub trait PromoteScalar<P> {
+    type Output: Clone + Debug + Default;
+    ///Returns the promoted array element and the promoted scalar, in that order.
+    fn promote_scalar(self, scalar: P) -> (Self::Output, Self::Output);
+}
+
+pub type Promoted<T1, T2> = <T1 as Promote<T2>>::Output;
+pub type PromotedScalar<T, P> = <T as PromoteScalar<P>>::Output;
+
+impl<T: Clone + Debug + Default> Promote<T> for T {
+    type Output = T;
+    fn promote(self, rhs: T) -> (T, T) {
+        (self, rhs)
+    }
+}
+
+impl<'a, T: Clone + Debug + Default> Promote<&'a T> for T {
+    type Output = T;
+    fn promote(self, rhs: &'a T) -> (T, T) {
+        (self, rhs.clone())
+    }
+}
+
+impl<T: Clone + Debug + Default> PromoteScalar<T> for T {
+    type Output = T;
+    fn promote_scalar(self, scalar: T) -> (T, T) {
+        (self, scalar)
+    }
+}
+
+impl<'a, T: Clone + Debug + Default> PromoteScalar<&'a T> for T {
+    type Output = T;
+    fn promote_scalar(self, scalar: &'a T) -> (T, T) {
+        (self, scalar.clone())
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T: Num + Copy + Debug + Default> Promote<C<T>> for T {
+    type Output = C<T>;
+    fn promote(self, rhs: C<T>) -> (C<T>, C<T>) {
+        (C(self, T::zero()), rhs)
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T: Num + Copy + Debug + Default> Promote<T> for C<T> {
+    type Output = C<T>;
+    fn promote(self, rhs: T) -> (C<T>, C<T>) {
+        (self, C(rhs, T::zero()))
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T: Num + Copy + Debug + Default> PromoteScalar<C<T>> for T {
+    type Output = C<T>;
+    fn promote_scalar(self, scalar: C<T>) -> (C<T>, C<T>) {
+        (C(self, T::zero()), scalar)
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T: Num + Copy + Debug + Default> PromoteScalar<T> for C<T> {
+    type Output = C<T>;
+    fn promote_scalar(self, scalar: T) -> (C<T>, C<T>) {
+        (self, C(scalar, T::zero()))
+    }
+}
+
+macro_rules! promote {
+    ($A:ty, $B:ty => $R:ty) => {
+        impl Promote<$B> for $A {
+            type Output = $R;
+            fn promote(self, rhs: $B) -> ($R, $R) {
+                (self as $R, rhs as $R)
+            }
+        }
+        impl Promote<$A> for $B {
+            type Output = $R;
+            fn promote(self, rhs: $A) -> ($R, $R) {
+                (self as $R, rhs as $R)
+            }
+        }
+        #[cfg(feature = "complex")]
+        impl Promote<C<$B>> for C<$A> {
+            type Output = C<$R>;
+            fn promote(self, rhs: C<$B>) -> (C<$R>, C<$R>) {
+                (C(self.0 as $R, self.1 as $R), C(rhs.0 as $R, rhs.1 as $R))
+            }
+        }
+        #[cfg(feature = "complex")]
+        impl Promote<C<$A>> for C<$B> {
+            type Output = C<$R>;
+            fn promote(self, rhs: C<$A>) -> (C<$R>, C<$R>) {
+                (C(self.0 as $R, self.1 as $R), C(rhs.0 as $R, rhs.1 as $R))
+            }
+        }
+        #[cfg(feature = "complex")]
+        impl Promote<$B> for C<$A> {
+            type Output = C<$R>;
+            fn promote(self, rhs: $B) -> (C<$R>, C<$R>) {
+                (C(self.0 as $R, self.1 as $R), C(rhs as $R, 0 as $R))
+            }
+        }
+        #[cfg(feature = "complex")]
+        impl Promote<$A> for C<$B> {
+            type Output = C<$R>;
+            fn promote(self, rhs: $A) -> (C<$R>, C<$R>) {
+                (C(self.0 as $R, self.1 as $R), C(rhs as $R, 0 as $R))
+            }
+        }
+        #[cfg(feature = "complex")]
+        impl Promote<C<$B>> for $A {
+            type Output = C<$R>;
+            fn promote(self, rhs: C<$B>) -> (C<$R>, C<$R>) {
+                (C(self as $R, 0 as $R), C(rhs.0 as $R, rhs.1 as $R))
+            }
+        }
+        #[cfg(feature = "complex")]
+        impl Promote<C<$A>> for $B {
+            type Output = C<$R>;
+            fn promote(self, rhs: C<$A>) -> (C<$R>, C<$R>) {
+                (C(self as $R, 0 as $R), C(rhs.0 as $R, rhs.1 as $R))
+            }
+        }
+    };
+}
+
+//Since the array type wins, a scalar outside of its range can't be widened, it panics instead of silently wrapping.
+fn cast_scalar<S: NumCast + Copy + Debug, T: NumCast>(scalar: S) -> T {
+    T::from(scalar).unwrap_or_else(|| {
+        panic!(
+            "Scalar out of range: {:?} does not fit the array element type {}",
+            scalar,
+            std::any::type_name::<T>()
+        )
+    })
+}
+
+//$W is the weak promotion of array type $A with scalar type $B, $R the promotion used when the scalar is complex.
+macro_rules! promote_scalar {
+    ($A:ty, $B:ty => $W:ty, $R:ty) => {
+        impl PromoteScalar<$B> for $A {
+            type Output = $W;
+            fn promote_scalar(self, scalar: $B) -> ($W, $W) {
+                (self as $W, cast_scalar(scalar))
+            }
+        }
+        #[cfg(feature = "complex")]
+        impl PromoteScalar<$B> for C<$A> {
+            type Output = C<$A>;
+            fn promote_scalar(self, scalar: $B) -> (C<$A>, C<$A>) {
+                (self, C(cast_scalar(scalar), 0 as $A))
+            }
+        }
+        #[cfg(feature = "complex")]
+        impl PromoteScalar<C<$B>> for C<$A> {
+            type Output = C<$A>;
+            fn promote_scalar(self, scalar: C<$B>) -> (C<$A>, C<$A>) {
+                (self, C(cast_scalar(scalar.0), cast_scalar(scalar.1)))
+            }
+        }
+        #[cfg(feature = "complex")]
+        impl PromoteScalar<C<$B>> for $A {
+            type Output = C<$R>;
+            fn promote_scalar(self, scalar: C<$B>) -> (C<$R>, C<$R>) {
+                (C(self as $R, 0 as $R), C(scalar.0 as $R, scalar.1 as $R))
+            }
+        }
+    };
+}
+
+promote!(u8, u16 => u16);
+promote!(u8, u32 => u32);
+promote!(u8, u64 => u64);
+promote!(u8, u128 => u128);
+promote!(u8, usize => usize);
+promote!(u8, i8 => i16);
+promote!(u8, i16 => i16);
+promote!(u8, i32 => i32);
+promote!(u8, i64 => i64);
+promote!(u8, i128 => i128);
+promote!(u8, isize => isize);
+promote!(u8, f32 => f32);
+promote!(u8, f64 => f64);
+promote!(u16, u32 => u32);
+promote!(u16, u64 => u64);
+promote!(u16, u128 => u128);
+promote!(u16, usize => usize);
+promote!(u16, i8 => i32);
+promote!(u16, i16 => i32);
+promote!(u16, i32 => i32);
+promote!(u16, i64 => i64);
+promote!(u16, i128 => i128);
+promote!(u16, isize => isize);
+promote!(u16, f32 => f32);
+promote!(u16, f64 => f64);
+promote!(u32, u64 => u64);
+promote!(u32, u128 => u128);
+promote!(u32, usize => usize);
+promote!(u32, i8 => i64);
+promote!(u32, i16 => i64);
+promote!(u32, i32 => i64);
+promote!(u32, i64 => i64);
+promote!(u32, i128 => i128);
+promote!(u32, isize => isize);
+promote!(u32, f32 => f64);
+promote!(u32, f64 => f64);
+promote!(u64, u128 => u128);
+promote!(u64, usize => u64);
+promote!(u64, i8 => i128);
+promote!(u64, i16 => i128);
+promote!(u64, i32 => i128);
+promote!(u64, i64 => i128);
+promote!(u64, i128 => i128);
+promote!(u64, isize => i128);
+promote!(u64, f32 => f64);
+promote!(u64, f64 => f64);
+promote!(u128, usize => u128);
+promote!(u128, i8 => f64);
+promote!(u128, i16 => f64);
+promote!(u128, i32 => f64);
+promote!(u128, i64 => f64);
+promote!(u128, i128 => f64);
+promote!(u128, isize => f64);
+promote!(u128, f32 => f64);
+promote!(u128, f64 => f64);
+promote!(usize, i8 => i128);
+promote!(usize, i16 => i128);
+promote!(usize, i32 => i128);
+promote!(usize, i64 => i128);
+promote!(usize, i128 => i128);
+promote!(usize, isize => i128);
+promote!(usize, f32 => f64);
+promote!(usize, f64 => f64);
+promote!(i8, i16 => i16);
+promote!(i8, i32 => i32);
+promote!(i8, i64 => i64);
+promote!(i8, i128 => i128);
+promote!(i8, isize => isize);
+promote!(i8, f32 => f32);
+promote!(i8, f64 => f64);
+promote!(i16, i32 => i32);
+promote!(i16, i64 => i64);
+promote!(i16, i128 => i128);
+promote!(i16, isize => isize);
+promote!(i16, f32 => f32);
+promote!(i16, f64 => f64);
+promote!(i32, i64 => i64);
+promote!(i32, i128 => i128);
+promote!(i32, isize => isize);
+promote!(i32, f32 => f64);
+promote!(i32, f64 => f64);
+promote!(i64, i128 => i128);
+promote!(i64, isize => i64);
+promote!(i64, f32 => f64);
+promote!(i64, f64 => f64);
+promote!(i128, isize => i128);
+promote!(i128, f32 => f64);
+promote!(i128, f64 => f64);
+promote!(isize, f32 => f64);
+promote!(isize, f64 => f64);
+promote!(f32, f64 => f64);
+
+promote_scalar!(u8, u16 => u8, u16);
+promote_scalar!(u8, u32 => u8, u32);
+promote_scalar!(u8, u64 => u8, u64);
+promote_scalar!(u8, u128 => u8, u128);
+promote_scalar!(u8, usize => u8, usize);
+promote_scalar!(u8, i8 => u8, i16);
+promote_scalar!(u8, i16 => u8, i16);
+promote_scalar!(u8, i32 => u8, i32);
+promote_scalar!(u8, i64 => u8, i64);
+promote_scalar!(u8, i128 => u8, i128);
+promote_scalar!(u8, isize => u8, isize);
+promote_scalar!(u8, f32 => f32, f32);
+promote_scalar!(u8, f64 => f64, f64);
+promote_scalar!(u16, u8 => u16, u16);
+promote_scalar!(u16, u32 => u16, u32);
+promote_scalar!(u16, u64 => u16, u64);
+promote_scalar!(u16, u128 => u16, u128);
+promote_scalar!(u16, usize => u16, usize);
+promote_scalar!(u16, i8 => u16, i32);
+promote_scalar!(u16, i16 => u16, i32);
+promote_scalar!(u16, i32 => u16, i32);
+promote_scalar!(u16, i64 => u16, i64);
+promote_scalar!(u16, i128 => u16, i128);
+promote_scalar!(u16, isize => u16, isize);
+promote_scalar!(u16, f32 => f32, f32);
+promote_scalar!(u16, f64 => f64, f64);
+promote_scalar!(u32, u8 => u32, u32);
+promote_scalar!(u32, u16 => u32, u32);
+promote_scalar!(u32, u64 => u32, u64);
+promote_scalar!(u32, u128 => u32, u128);
+promote_scalar!(u32, usize => u32, usize);
+promote_scalar!(u32, i8 => u32, i64);
+promote_scalar!(u32, i16 => u32, i64);
+promote_scalar!(u32, i32 => u32, i64);
+promote_scalar!(u32, i64 => u32, i64);
+promote_scalar!(u32, i128 => u32, i128);
+promote_scalar!(u32, isize => u32, isize);
+promote_scalar!(u32, f32 => f64, f64);
+promote_scalar!(u32, f64 => f64, f64);
+promote_scalar!(u64, u8 => u64, u64);
+promote_scalar!(u64, u16 => u64, u64);
+promote_scalar!(u64, u32 => u64, u64);
+promote_scalar!(u64, u128 => u64, u128);
+promote_scalar!(u64, usize => u64, u64);
+promote_scalar!(u64, i8 => u64, i128);
+promote_scalar!(u64, i16 => u64, i128);
+promote_scalar!(u64, i32 => u64, i128);
+promote_scalar!(u64, i64 => u64, i128);
+promote_scalar!(u64, i128 => u64, i128);
+promote_scalar!(u64, isize => u64, i128);
+promote_scalar!(u64, f32 => f64, f64);
+promote_scalar!(u64, f64 => f64, f64);
+promote_scalar!(u128, u8 => u128, u128);
+promote_scalar!(u128, u16 => u128, u128);
+promote_scalar!(u128, u32 => u128, u128);
+promote_scalar!(u128, u64 => u128, u128);
+promote_scalar!(u128, usize => u128, u128);
+promote_scalar!(u128, i8 => u128, f64);
+promote_scalar!(u128, i16 => u128, f64);
+promote_scalar!(u128, i32 => u128, f64);
+promote_scalar!(u128, i64 => u128, f64);
+promote_scalar!(u128, i128 => u128, f64);
+promote_scalar!(u128, isize => u128, f64);
+promote_scalar!(u128, f32 => f64, f64);
+promote_scalar!(u128, f64 => f64, f64);
+promote_scalar!(usize, u8 => usize, usize);
+promote_scalar!(usize, u16 => usize, usize);
+promote_scalar!(usize, u32 => usize, usize);
+promote_scalar!(usize, u64 => usize, u64);
+promote_scalar!(usize, u128 => usize, u128);
+promote_scalar!(usize, i8 => usize, i128);
+promote_scalar!(usize, i16 => usize, i128);
+promote_scalar!(usize, i32 => usize, i128);
+promote_scalar!(usize, i64 => usize, i128);
+promote_scalar!(usize, i128 => usize, i128);
+promote_scalar!(usize, isize => usize, i128);
+promote_scalar!(usize, f32 => f64, f64);
+promote_scalar!(usize, f64 => f64, f64);
+promote_scalar!(i8, u8 => i8, i16);
+promote_scalar!(i8, u16 => i8, i32);
+promote_scalar!(i8, u32 => i8, i64);
+promote_scalar!(i8, u64 => i8, i128);
+promote_scalar!(i8, u128 => i8, f64);
+promote_scalar!(i8, usize => i8, i128);
+promote_scalar!(i8, i16 => i8, i16);
+promote_scalar!(i8, i32 => i8, i32);
+promote_scalar!(i8, i64 => i8, i64);
+promote_scalar!(i8, i128 => i8, i128);
+promote_scalar!(i8, isize => i8, isize);
+promote_scalar!(i8, f32 => f32, f32);
+promote_scalar!(i8, f64 => f64, f64);
+promote_scalar!(i16, u8 => i16, i16);
+promote_scalar!(i16, u16 => i16, i32);
+promote_scalar!(i16, u32 => i16, i64);
+promote_scalar!(i16, u64 => i16, i128);
+promote_scalar!(i16, u128 => i16, f64);
+promote_scalar!(i16, usize => i16, i128);
+promote_scalar!(i16, i8 => i16, i16);
+promote_scalar!(i16, i32 => i16, i32);
+promote_scalar!(i16, i64 => i16, i64);
+promote_scalar!(i16, i128 => i16, i128);
+promote_scalar!(i16, isize => i16, isize);
+promote_scalar!(i16, f32 => f32, f32);
+promote_scalar!(i16, f64 => f64, f64);
+promote_scalar!(i32, u8 => i32, i32);
+promote_scalar!(i32, u16 => i32, i32);
+promote_scalar!(i32, u32 => i32, i64);
+promote_scalar!(i32, u64 => i32, i128);
+promote_scalar!(i32, u128 => i32, f64);
+promote_scalar!(i32, usize => i32, i128);
+promote_scalar!(i32, i8 => i32, i32);
+promote_scalar!(i32, i16 => i32, i32);
+promote_scalar!(i32, i64 => i32, i64);
+promote_scalar!(i32, i128 => i32, i128);
+promote_scalar!(i32, isize => i32, isize);
+promote_scalar!(i32, f32 => f64, f64);
+promote_scalar!(i32, f64 => f64, f64);
+promote_scalar!(i64, u8 => i64, i64);
+promote_scalar!(i64, u16 => i64, i64);
+promote_scalar!(i64, u32 => i64, i64);
+promote_scalar!(i64, u64 => i64, i128);
+promote_scalar!(i64, u128 => i64, f64);
+promote_scalar!(i64, usize => i64, i128);
+promote_scalar!(i64, i8 => i64, i64);
+promote_scalar!(i64, i16 => i64, i64);
+promote_scalar!(i64, i32 => i64, i64);
+promote_scalar!(i64, i128 => i64, i128);
+promote_scalar!(i64, isize => i64, i64);
+promote_scalar!(i64, f32 => f64, f64);
+promote_scalar!(i64, f64 => f64, f64);
+promote_scalar!(i128, u8 => i128, i128);
+promote_scalar!(i128, u16 => i128, i128);
+promote_scalar!(i128, u32 => i128, i128);
+promote_scalar!(i128, u64 => i128, i128);
+promote_scalar!(i128, u128 => i128, f64);
+promote_scalar!(i128, usize => i128, i128);
+promote_scalar!(i128, i8 => i128, i128);
+promote_scalar!(i128, i16 => i128, i128);
+promote_scalar!(i128, i32 => i128, i128);
+promote_scalar!(i128, i64 => i128, i128);
+promote_scalar!(i128, isize => i128, i128);
+promote_scalar!(i128, f32 => f64, f64);
+promote_scalar!(i128, f64 => f64, f64);
+promote_scalar!(isize, u8 => isize, isize);
+promote_scalar!(isize, u16 => isize, isize);
+promote_scalar!(isize, u32 => isize, isize);
+promote_scalar!(isize, u64 => isize, i128);
+promote_scalar!(isize, u128 => isize, f64);
+promote_scalar!(isize, usize => isize, i128);
+promote_scalar!(isize, i8 => isize, isize);
+promote_scalar!(isize, i16 => isize, isize);
+promote_scalar!(isize, i32 => isize, isize);
+promote_scalar!(isize, i64 => isize, i64);
+promote_scalar!(isize, i128 => isize, i128);
+promote_scalar!(isize, f32 => f64, f64);
+promote_scalar!(isize, f64 => f64, f64);
+promote_scalar!(f32, u8 => f32, f32);
+promote_scalar!(f32, u16 => f32, f32);
+promote_scalar!(f32, u32 => f32, f64);
+promote_scalar!(f32, u64 => f32, f64);
+promote_scalar!(f32, u128 => f32, f64);
+promote_scalar!(f32, usize => f32, f64);
+promote_scalar!(f32, i8 => f32, f32);
+promote_scalar!(f32, i16 => f32, f32);
+promote_scalar!(f32, i32 => f32, f64);
+promote_scalar!(f32, i64 => f32, f64);
+promote_scalar!(f32, i128 => f32, f64);
+promote_scalar!(f32, isize => f32, f64);
+promote_scalar!(f32, f64 => f32, f64);
+promote_scalar!(f64, u8 => f64, f64);
+promote_scalar!(f64, u16 => f64, f64);
+promote_scalar!(f64, u32 => f64, f64);
+promote_scalar!(f64, u64 => f64, f64);
+promote_scalar!(f64, u128 => f64, f64);
+promote_scalar!(f64, usize => f64, f64);
+promote_scalar!(f64, i8 => f64, f64);
+promote_scalar!(f64, i16 => f64, f64);
+promote_scalar!(f64, i32 => f64, f64);
+promote_scalar!(f64, i64 => f64, f64);
+promote_scalar!(f64, i128 => f64, f64);
+promote_scalar!(f64, isize => f64, f64);
+promote_scalar!(f64, f32 => f64, f64);
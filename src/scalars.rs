@@ -20,6 +20,7 @@ impl Scalar for u16 {}
 impl Scalar for u8 {}
 impl Scalar for usize {}
 impl Scalar for char {}
+impl Scalar for bool {}
 
 impl Scalar for &f64 {}
 impl Scalar for &f32 {}
@@ -36,6 +37,7 @@ impl Scalar for &u16 {}
 impl Scalar for &u8 {}
 impl Scalar for &usize {}
 impl Scalar for &char {}
+impl Scalar for &bool {}
 impl Scalar for &str {}
 
 pub fn extend_scalar<P, T, R: Unsigned>(scalar: &P, shape: &Dim<R>) -> Ndarr<T, R>
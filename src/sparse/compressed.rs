@@ -0,0 +1,663 @@
+use super::*;
+use num_traits::One;
+use std::ops::Neg;
+
+//Compressed storage: the elements of the outer line `o`, a row for CSR and a column for CSC, are at the positions
+//`indptr[o]..indptr[o + 1]` of `indices` and `values`, with increasing inner indices.
+#[derive(Debug, Clone, PartialEq)]
+struct Compressed<T> {
+    outer: usize,
+    inner: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: SparseScalar> Compressed<T> {
+    fn new(
+        outer: usize,
+        inner: usize,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<Self, DimError> {
+        if indptr.len() != outer + 1
+            || indptr[0] != 0
+            || indptr[outer] != indices.len()
+            || indices.len() != values.len()
+        {
+            return Err(DimError::new(&format!(
+                "Expected {} pointers starting at 0 and ending at the number of indices and values, found {}, {} indices and {} values.",
+                outer + 1,
+                indptr.len(),
+                indices.len(),
+                values.len()
+            )));
+        }
+        //with the last pointer equal to the number of indices, this keeps every line in bounds
+        if indptr.windows(2).any(|w| w[0] > w[1]) {
+            return Err(DimError::new("Pointers must be non decreasing."));
+        }
+        for o in 0..outer {
+            let line = &indices[indptr[o]..indptr[o + 1]];
+            if line.windows(2).any(|w| w[0] >= w[1]) || line.last().is_some_and(|i| *i >= inner) {
+                return Err(DimError::new(&format!(
+                    "Indices of every line must be increasing and smaller than {}.",
+                    inner
+                )));
+            }
+        }
+        Ok(Compressed {
+            outer,
+            inner,
+            indptr,
+            indices,
+            values,
+        })
+    }
+
+    //Sorts `(outer, inner, value)` triplets and sums the duplicates, the indices must be in bounds.
+    fn from_triplets(outer: usize, inner: usize, mut triplets: Vec<(usize, usize, T)>) -> Self {
+        triplets.sort_by_key(|t| (t.0, t.1));
+        let mut indptr = vec![0; outer + 1];
+        let mut indices = Vec::with_capacity(triplets.len());
+        let mut values: Vec<T> = Vec::with_capacity(triplets.len());
+        let mut last = None;
+        for (o, i, v) in triplets {
+            if last == Some((o, i)) {
+                let sum = values.last_mut().unwrap();
+                *sum = *sum + v;
+            } else {
+                indptr[o + 1] += 1;
+                indices.push(i);
+                values.push(v);
+                last = Some((o, i));
+            }
+        }
+        for o in 0..outer {
+            indptr[o + 1] += indptr[o];
+        }
+        Compressed {
+            outer,
+            inner,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    fn line(&self, o: usize) -> std::ops::Range<usize> {
+        self.indptr[o]..self.indptr[o + 1]
+    }
+
+    fn get(&self, o: usize, i: usize) -> T {
+        let line = self.line(o);
+        match self.indices[line.clone()].binary_search(&i) {
+            Ok(p) => self.values[line.start + p],
+            Err(_) => T::zero(),
+        }
+    }
+
+    //The same matrix with the roles of the outer and inner indices swapped.
+    fn switch(&self) -> Self {
+        let mut indptr = vec![0; self.inner + 1];
+        for i in &self.indices {
+            indptr[i + 1] += 1;
+        }
+        for i in 0..self.inner {
+            indptr[i + 1] += indptr[i];
+        }
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.indices.len()];
+        let mut values = vec![T::zero(); self.values.len()];
+        for o in 0..self.outer {
+            for p in self.line(o) {
+                let q = &mut next[self.indices[p]];
+                indices[*q] = o;
+                values[*q] = self.values[p];
+                *q += 1;
+            }
+        }
+        Compressed {
+            outer: self.inner,
+            inner: self.outer,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    fn map<U: SparseScalar, F: Fn(T) -> U>(&self, f: F) -> Compressed<U> {
+        Compressed {
+            outer: self.outer,
+            inner: self.inner,
+            indptr: self.indptr.clone(),
+            indices: self.indices.clone(),
+            values: self.values.iter().map(|v| f(*v)).collect(),
+        }
+    }
+
+    //Elementwise operation on matrices of the same shape, `union` keeps the elements stored in only one of them.
+    //Zero results are not stored.
+    fn merge<F: Fn(T, T) -> T>(&self, other: &Self, f: F, union: bool) -> Self {
+        let zero = T::zero();
+        let mut indptr = Vec::with_capacity(self.outer + 1);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        indptr.push(0);
+        for o in 0..self.outer {
+            let (mut p, p_end) = (self.indptr[o], self.indptr[o + 1]);
+            let (mut q, q_end) = (other.indptr[o], other.indptr[o + 1]);
+            while p < p_end || q < q_end {
+                let (i, v) = if q == q_end || (p < p_end && self.indices[p] < other.indices[q]) {
+                    p += 1;
+                    (
+                        self.indices[p - 1],
+                        union.then(|| f(self.values[p - 1], zero)),
+                    )
+                } else if p == p_end || other.indices[q] < self.indices[p] {
+                    q += 1;
+                    (
+                        other.indices[q - 1],
+                        union.then(|| f(zero, other.values[q - 1])),
+                    )
+                } else {
+                    p += 1;
+                    q += 1;
+                    (
+                        self.indices[p - 1],
+                        Some(f(self.values[p - 1], other.values[q - 1])),
+                    )
+                };
+                if let Some(v) = v.filter(|v| *v != zero) {
+                    indices.push(i);
+                    values.push(v);
+                }
+            }
+            indptr.push(indices.len());
+        }
+        Compressed {
+            outer: self.outer,
+            inner: self.inner,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    //Row by row product of `self` and `other` as CSR matrices, with `self.inner == other.outer`.
+    fn product(&self, other: &Self) -> Self {
+        let zero = T::zero();
+        let mut acc = vec![zero; other.inner];
+        let mut seen = vec![usize::MAX; other.inner];
+        let mut indptr = Vec::with_capacity(self.outer + 1);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        indptr.push(0);
+        for o in 0..self.outer {
+            let mut line = Vec::new();
+            for p in self.line(o) {
+                let a = self.values[p];
+                for q in other.line(self.indices[p]) {
+                    let j = other.indices[q];
+                    if seen[j] != o {
+                        seen[j] = o;
+                        acc[j] = zero;
+                        line.push(j);
+                    }
+                    acc[j] = acc[j] + a * other.values[q];
+                }
+            }
+            line.sort_unstable();
+            for j in line {
+                if acc[j] != zero {
+                    indices.push(j);
+                    values.push(acc[j]);
+                }
+            }
+            indptr.push(indices.len());
+        }
+        Compressed {
+            outer: self.outer,
+            inner: other.inner,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    fn kron(&self, other: &Self) -> Self {
+        let mut indptr = Vec::with_capacity(self.outer * other.outer + 1);
+        let mut indices = Vec::with_capacity(self.values.len() * other.values.len());
+        let mut values = Vec::with_capacity(self.values.len() * other.values.len());
+        indptr.push(0);
+        for o1 in 0..self.outer {
+            for o2 in 0..other.outer {
+                for p in self.line(o1) {
+                    for q in other.line(o2) {
+                        indices.push(self.indices[p] * other.inner + other.indices[q]);
+                        values.push(self.values[p] * other.values[q]);
+                    }
+                }
+                indptr.push(indices.len());
+            }
+        }
+        Compressed {
+            outer: self.outer * other.outer,
+            inner: self.inner * other.inner,
+            indptr,
+            indices,
+            values,
+        }
+    }
+}
+
+///Sparse matrix in compressed sparse row format: the column indices and values of row `i` are at the positions
+///`indptr[i]..indptr[i + 1]` of `indices` and `values`. Efficient for products with vectors and row access.
+///```
+///use rapl::*;
+///use rapl::sparse::CsrMatrix;
+///let a = CsrMatrix::from_dense(&Ndarr::from([[2., 0., 0.], [0., 0., 1.]]));
+///assert_eq!((a.nnz(), a.indptr(), a.indices()), (2, &[0, 1, 2][..], &[0, 2][..]));
+///assert_eq!(a.mat_mul(&Ndarr::from([1., 1., 3.])).unwrap(), Ndarr::from([2., 3.]));
+///```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix<T> {
+    storage: Compressed<T>,
+}
+
+///Sparse matrix in compressed sparse column format: the row indices and values of column `j` are at the positions
+///`indptr[j]..indptr[j + 1]` of `indices` and `values`. Efficient for column access.
+///```
+///use rapl::*;
+///use rapl::sparse::CscMatrix;
+///let a = CscMatrix::from_dense(&Ndarr::from([[2., 0., 0.], [0., 0., 1.]]));
+///assert_eq!((a.nnz(), a.indptr(), a.indices()), (2, &[0, 1, 1, 2][..], &[0, 1][..]));
+///assert_eq!(a.to_csr().to_dense(), a.to_dense());
+///```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CscMatrix<T> {
+    storage: Compressed<T>,
+}
+
+macro_rules! compressed_matrix {
+    ($Name:ident, $row_major:expr) => {
+        impl<T: SparseScalar> $Name<T> {
+            //Outer and inner index of the element `[i, j]`, and the other way around.
+            fn key(i: usize, j: usize) -> (usize, usize) {
+                if $row_major {
+                    (i, j)
+                } else {
+                    (j, i)
+                }
+            }
+
+            ///Matrix of shape `shape` from its compressed arrays. Returns Error if the arrays are inconsistent
+            ///or the indices of a line are not increasing.
+            pub fn new(
+                shape: [usize; 2],
+                indptr: Vec<usize>,
+                indices: Vec<usize>,
+                values: Vec<T>,
+            ) -> Result<Self, DimError> {
+                let (outer, inner) = Self::key(shape[0], shape[1]);
+                Ok($Name {
+                    storage: Compressed::new(outer, inner, indptr, indices, values)?,
+                })
+            }
+
+            //Matrix from `(row, column, value)` triplets in bounds, duplicates are summed.
+            pub(super) fn from_triplets(shape: [usize; 2], triplets: Vec<(usize, usize, T)>) -> Self {
+                let (outer, inner) = Self::key(shape[0], shape[1]);
+                let triplets = triplets
+                    .into_iter()
+                    .map(|(i, j, v)| {
+                        let (o, n) = Self::key(i, j);
+                        (o, n, v)
+                    })
+                    .collect();
+                $Name {
+                    storage: Compressed::from_triplets(outer, inner, triplets),
+                }
+            }
+
+            ///Matrix of shape `shape` without stored elements.
+            pub fn zeros(shape: [usize; 2]) -> Self {
+                Self::from_triplets(shape, Vec::new())
+            }
+
+            ///Identity matrix of size `n`.
+            pub fn identity(n: usize) -> Self
+            where
+                T: One,
+            {
+                Self::from_triplets([n, n], (0..n).map(|i| (i, i, T::one())).collect())
+            }
+
+            ///Sparse matrix with the nonzero elements of a dense matrix.
+            pub fn from_dense(a: &Ndarr<T, U2>) -> Self {
+                let n = a.shape()[1];
+                let triplets = a
+                    .data
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, v)| **v != T::zero())
+                    .map(|(p, v)| (p / n, p % n, *v))
+                    .collect();
+                Self::from_triplets([a.shape()[0], n], triplets)
+            }
+
+            ///Dense matrix.
+            pub fn to_dense(&self) -> Ndarr<T, U2> {
+                let [m, n] = self.shape();
+                let mut data = vec![T::zero(); m * n];
+                for (i, j, v) in self.triplets() {
+                    data[i * n + j] = v;
+                }
+                Ndarr {
+                    data,
+                    dim: Dim::new(&[m, n]).unwrap(),
+                }
+            }
+
+            ///Matrix in coordinate format.
+            pub fn to_coo(&self) -> CooMatrix<T> {
+                let mut coo = CooMatrix::new(self.shape());
+                for (i, j, v) in self.triplets() {
+                    coo.push(i, j, v).unwrap();
+                }
+                coo
+            }
+
+            //`(row, column, value)` of every stored element.
+            fn triplets(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+                let s = &self.storage;
+                (0..s.outer).flat_map(move |o| {
+                    s.line(o).map(move |p| {
+                        let (i, j) = Self::key(o, s.indices[p]);
+                        (i, j, s.values[p])
+                    })
+                })
+            }
+
+            pub fn shape(&self) -> [usize; 2] {
+                let (m, n) = Self::key(self.storage.outer, self.storage.inner);
+                [m, n]
+            }
+
+            ///Number of stored elements.
+            pub fn nnz(&self) -> usize {
+                self.storage.values.len()
+            }
+
+            pub fn indptr(&self) -> &[usize] {
+                &self.storage.indptr
+            }
+
+            pub fn indices(&self) -> &[usize] {
+                &self.storage.indices
+            }
+
+            pub fn values(&self) -> &[T] {
+                &self.storage.values
+            }
+
+            ///Element `[i, j]`, zero if it is not stored. Panics if the index is out of bounds.
+            pub fn get(&self, i: usize, j: usize) -> T {
+                check_index(self.shape(), i, j).unwrap();
+                let (o, n) = Self::key(i, j);
+                self.storage.get(o, n)
+            }
+
+            ///Transpose, in the same format.
+            pub fn t(&self) -> Self {
+                $Name {
+                    storage: self.storage.switch(),
+                }
+            }
+
+            ///Applies `f` to every stored element, the sparsity pattern is preserved.
+            pub fn map<U: SparseScalar, F: Fn(T) -> U>(&self, f: F) -> $Name<U> {
+                $Name {
+                    storage: self.storage.map(f),
+                }
+            }
+
+            ///Multiplies every element by `x`.
+            pub fn scale(&self, x: T) -> Self {
+                self.map(|v| v * x)
+            }
+
+            ///Product with a dense vector or matrix. Returns Error if the number of rows of `x` is not the number
+            ///of columns of the sparse matrix.
+            pub fn mat_mul<R: Unsigned>(&self, x: &Ndarr<T, R>) -> Result<Ndarr<T, R>, DimError> {
+                let [m, n] = self.shape();
+                let k = check_dense(x, n)?;
+                let mut shape = x.shape().to_vec();
+                shape[0] = m;
+                let mut data = vec![T::zero(); m * k];
+                for (i, j, v) in self.triplets() {
+                    for l in 0..k {
+                        data[i * k + l] = data[i * k + l] + v * x.data[j * k + l];
+                    }
+                }
+                Ok(Ndarr {
+                    data,
+                    dim: Dim::new(&shape)?,
+                })
+            }
+
+            ///Product of two sparse matrices. Returns Error if the shapes are not compatible.
+            pub fn mat_mul_sparse(&self, other: &Self) -> Result<Self, DimError> {
+                if self.shape()[1] != other.shape()[0] {
+                    return Err(DimError::new(&format!(
+                        "Shapes {:?} and {:?} are not compatible for a matrix product.",
+                        self.shape(),
+                        other.shape()
+                    )));
+                }
+                //a CSC matrix is the CSR storage of its transpose, and (A B)^T = B^T A^T
+                let storage = if $row_major {
+                    self.storage.product(&other.storage)
+                } else {
+                    other.storage.product(&self.storage)
+                };
+                Ok($Name { storage })
+            }
+
+            ///Kronecker product, the block matrix with blocks `self[i, j] * other`.
+            pub fn kron(&self, other: &Self) -> Self {
+                $Name {
+                    storage: self.storage.kron(&other.storage),
+                }
+            }
+
+            //Elementwise operation, panics if the shapes differ.
+            fn zip_with<F: Fn(T, T) -> T>(&self, other: &Self, f: F, union: bool) -> Self {
+                if self.shape() != other.shape() {
+                    panic!(
+                        "Shapes {:?} and {:?} are not compatible for an elementwise operation.",
+                        self.shape(),
+                        other.shape()
+                    );
+                }
+                $Name {
+                    storage: self.storage.merge(&other.storage, f, union),
+                }
+            }
+        }
+
+        sparse_op!($Name<T>, $Name<T>, $Name<T>, Add, add, +, true);
+        sparse_op!($Name<T>, &$Name<T>, $Name<T>, Add, add, +, true);
+        sparse_op!(&$Name<T>, $Name<T>, $Name<T>, Add, add, +, true);
+        sparse_op!(&$Name<T>, &$Name<T>, $Name<T>, Add, add, +, true);
+
+        sparse_op!($Name<T>, $Name<T>, $Name<T>, Sub, sub, -, true);
+        sparse_op!($Name<T>, &$Name<T>, $Name<T>, Sub, sub, -, true);
+        sparse_op!(&$Name<T>, $Name<T>, $Name<T>, Sub, sub, -, true);
+        sparse_op!(&$Name<T>, &$Name<T>, $Name<T>, Sub, sub, -, true);
+
+        //the elementwise product only needs the elements stored in both matrices
+        sparse_op!($Name<T>, $Name<T>, $Name<T>, Mul, mul, *, false);
+        sparse_op!($Name<T>, &$Name<T>, $Name<T>, Mul, mul, *, false);
+        sparse_op!(&$Name<T>, $Name<T>, $Name<T>, Mul, mul, *, false);
+        sparse_op!(&$Name<T>, &$Name<T>, $Name<T>, Mul, mul, *, false);
+
+        impl<T: SparseScalar + Neg<Output = T>> Neg for $Name<T> {
+            type Output = $Name<T>;
+            fn neg(self) -> Self::Output {
+                self.map(|v| -v)
+            }
+        }
+
+        impl<T: SparseScalar + Neg<Output = T>> Neg for &$Name<T> {
+            type Output = $Name<T>;
+            fn neg(self) -> Self::Output {
+                self.map(|v| -v)
+            }
+        }
+    };
+}
+
+//Elementwise operators, panic if the shapes differ like the operators of `Ndarr`.
+macro_rules! sparse_op {
+    ($Ty1:ty, $Ty2:ty, $Out:ty, $Trait:tt, $F:tt, $Op:tt, $union:expr) => {
+        impl<T: SparseScalar> $Trait<$Ty2> for $Ty1 {
+            type Output = $Out;
+            fn $F(self, rhs: $Ty2) -> Self::Output {
+                self.zip_with(&rhs, |x, y| x $Op y, $union)
+            }
+        }
+    };
+}
+
+compressed_matrix!(CsrMatrix, true);
+compressed_matrix!(CscMatrix, false);
+
+impl<T: SparseScalar> CsrMatrix<T> {
+    ///The same matrix in compressed sparse column format.
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        CscMatrix {
+            storage: self.storage.switch(),
+        }
+    }
+}
+
+impl<T: SparseScalar> CscMatrix<T> {
+    ///The same matrix in compressed sparse row format.
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        CsrMatrix {
+            storage: self.storage.switch(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod compressed_test {
+    use super::*;
+
+    fn dense() -> Ndarr<i32, U2> {
+        Ndarr::from([[1, 0, 2, 0], [0, 0, 3, 0], [4, 5, 0, 6]])
+    }
+
+    #[test]
+    fn construction() {
+        let a = CsrMatrix::from_dense(&dense());
+        assert_eq!(a.shape(), [3, 4]);
+        assert_eq!(a.indptr(), &[0, 2, 3, 6]);
+        assert_eq!(a.indices(), &[0, 2, 2, 0, 1, 3]);
+        assert_eq!(a.values(), &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(a.to_dense(), dense());
+        assert_eq!((a.get(2, 1), a.get(1, 1)), (5, 0));
+        let c = a.to_csc();
+        assert_eq!(c.indptr(), &[0, 2, 3, 5, 6]);
+        assert_eq!(c.indices(), &[0, 2, 2, 0, 1, 2]);
+        assert_eq!(c, CscMatrix::from_dense(&dense()));
+        assert_eq!(c.to_csr(), a);
+        assert_eq!(c.to_dense(), dense());
+        assert_eq!(a.to_coo().to_csr(), a);
+        let b = CsrMatrix::new(
+            [3, 4],
+            vec![0, 2, 3, 6],
+            vec![0, 2, 2, 0, 1, 3],
+            vec![1, 2, 3, 4, 5, 6],
+        );
+        assert_eq!(b.unwrap(), a);
+        assert!(CsrMatrix::new([2, 2], vec![0, 1], vec![0], vec![1]).is_err());
+        assert!(CsrMatrix::new([2, 2], vec![0, 2, 2], vec![1, 0], vec![1, 1]).is_err());
+        assert!(CsrMatrix::new([2, 2], vec![0, 1, 1], vec![2], vec![1]).is_err());
+        assert!(CsrMatrix::new([2, 2], vec![0, 5, 2], vec![0, 1], vec![1., 1.]).is_err());
+        assert_eq!(CsrMatrix::<f64>::zeros([2, 3]).nnz(), 0);
+        assert_eq!(
+            CscMatrix::<i32>::identity(2).to_dense(),
+            Ndarr::from([[1, 0], [0, 1]])
+        );
+    }
+
+    #[test]
+    fn products() {
+        let a = CsrMatrix::from_dense(&dense());
+        let x = Ndarr::from([1, 2, 3, 4]);
+        assert_eq!(a.mat_mul(&x).unwrap(), dense().mat_mul(&x));
+        let m = Ndarr::from([[1, 0], [0, 1], [2, 2], [1, -1]]);
+        assert_eq!(a.mat_mul(&m).unwrap(), dense().mat_mul(&m));
+        assert_eq!(a.to_csc().mat_mul(&m).unwrap(), dense().mat_mul(&m));
+        assert!(a.mat_mul(&Ndarr::from([1, 2, 3])).is_err());
+        let at = a.t();
+        assert_eq!(at.to_dense(), dense().t());
+        let aat = a.mat_mul_sparse(&at).unwrap();
+        assert_eq!(aat.to_dense(), dense().mat_mul(&dense().t()));
+        let c = a.to_csc();
+        assert_eq!(
+            c.t().mat_mul_sparse(&c).unwrap().to_dense(),
+            dense().t().mat_mul(&dense())
+        );
+        assert!(a.mat_mul_sparse(&a).is_err());
+        //cancellations are not stored
+        let p = CsrMatrix::from_dense(&Ndarr::from([[1, 1]]));
+        let q = CsrMatrix::from_dense(&Ndarr::from([[1], [-1]]));
+        assert_eq!(p.mat_mul_sparse(&q).unwrap().nnz(), 0);
+    }
+
+    #[test]
+    fn elementwise() {
+        let a = CsrMatrix::from_dense(&dense());
+        let b = CsrMatrix::from_dense(&Ndarr::from([[1, 1, 0, 0], [0, 0, -3, 0], [0, 0, 0, 1]]));
+        assert_eq!((&a + &b).to_dense(), dense() + b.to_dense());
+        assert_eq!((a.clone() - b.clone()).to_dense(), dense() - b.to_dense());
+        assert_eq!((&a * b.clone()).to_dense(), dense() * b.to_dense());
+        assert_eq!((&a + &b).nnz(), 6);
+        assert_eq!((&a * &b).nnz(), 3);
+        assert_eq!((-&a).to_dense(), -dense());
+        assert_eq!(a.scale(2).to_dense(), dense() * 2);
+        assert_eq!(a.map(|x| x as f64 / 2.).get(2, 2), 0.);
+        let c = a.to_csc() - b.to_csc();
+        assert_eq!(c.to_dense(), dense() - b.to_dense());
+    }
+
+    #[test]
+    #[should_panic]
+    fn elementwise_shape() {
+        let _ = CsrMatrix::<i32>::identity(2) + CsrMatrix::identity(3);
+    }
+
+    #[test]
+    fn kronecker() {
+        let a = CsrMatrix::from_dense(&Ndarr::from([[1, 2], [0, 3]]));
+        let b = CsrMatrix::from_dense(&Ndarr::from([[0, 1], [1, 0]]));
+        let expected = Ndarr::from([[0, 1, 0, 2], [1, 0, 2, 0], [0, 0, 0, 3], [0, 0, 3, 0]]);
+        assert_eq!(a.kron(&b).to_dense(), expected);
+        assert_eq!(a.to_csc().kron(&b.to_csc()).to_dense(), expected);
+        //Laplacian of a 3 x 3 grid from the one of a chain, L = L1 x I + I x L1
+        let chain =
+            CsrMatrix::from_dense(&Ndarr::from([[1., -1., 0.], [-1., 2., -1.], [0., -1., 1.]]));
+        let id = CsrMatrix::identity(3);
+        let grid = chain.kron(&id) + id.kron(&chain);
+        assert_eq!(grid.shape(), [9, 9]);
+        assert_eq!(grid.get(4, 4), 4.);
+        assert_eq!(
+            grid.mat_mul(&Ndarr::from([1.; 9])).unwrap(),
+            Ndarr::from([0.; 9])
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use super::*;
+
+///Sparse matrix in coordinate format, a list of `(row, column, value)` triplets. Duplicate entries are summed when
+///converting to the other formats, which makes it convenient to assemble an operator term by term.
+///```
+///use rapl::*;
+///use rapl::sparse::CooMatrix;
+///let mut a = CooMatrix::new([2, 2]);
+///a.push(0, 0, 1.).unwrap();
+///a.push(1, 1, 2.).unwrap();
+///a.push(0, 0, 3.).unwrap();
+///assert_eq!(a.to_dense(), Ndarr::from([[4., 0.], [0., 2.]]));
+///assert_eq!(a.to_csr().nnz(), 2);
+///```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooMatrix<T> {
+    shape: [usize; 2],
+    rows: Vec<usize>,
+    cols: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: SparseScalar> CooMatrix<T> {
+    ///Empty matrix of shape `shape`.
+    pub fn new(shape: [usize; 2]) -> Self {
+        CooMatrix {
+            shape,
+            rows: Vec::new(),
+            cols: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    ///Matrix from the row indices, column indices and values of its elements.
+    ///Returns Error if the lengths differ or an index is out of bounds.
+    pub fn from_triplets(
+        shape: [usize; 2],
+        rows: Vec<usize>,
+        cols: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<Self, DimError> {
+        if rows.len() != values.len() || cols.len() != values.len() {
+            return Err(DimError::new(&format!(
+                "Expected the same number of rows, columns and values, found {}, {} and {}.",
+                rows.len(),
+                cols.len(),
+                values.len()
+            )));
+        }
+        for (i, j) in rows.iter().zip(&cols) {
+            check_index(shape, *i, *j)?;
+        }
+        Ok(CooMatrix {
+            shape,
+            rows,
+            cols,
+            values,
+        })
+    }
+
+    ///Adds the element `v` at `[i, j]`, summed with any previous element at the same position.
+    ///Returns Error if the index is out of bounds.
+    pub fn push(&mut self, i: usize, j: usize, v: T) -> Result<(), DimError> {
+        check_index(self.shape, i, j)?;
+        self.rows.push(i);
+        self.cols.push(j);
+        self.values.push(v);
+        Ok(())
+    }
+
+    ///Sparse matrix with the nonzero elements of a dense matrix.
+    pub fn from_dense(a: &Ndarr<T, U2>) -> Self {
+        CsrMatrix::from_dense(a).to_coo()
+    }
+
+    ///Dense matrix, duplicate entries are summed.
+    pub fn to_dense(&self) -> Ndarr<T, U2> {
+        let [m, n] = self.shape;
+        let mut data = vec![T::zero(); m * n];
+        for ((i, j), v) in self.rows.iter().zip(&self.cols).zip(&self.values) {
+            data[i * n + j] = data[i * n + j] + *v;
+        }
+        Ndarr {
+            data,
+            dim: Dim::new(&[m, n]).unwrap(),
+        }
+    }
+
+    //`(row, column, value)` of every element.
+    fn triplets(&self) -> Vec<(usize, usize, T)> {
+        self.rows
+            .iter()
+            .zip(&self.cols)
+            .zip(&self.values)
+            .map(|((i, j), v)| (*i, *j, *v))
+            .collect()
+    }
+
+    ///Matrix in compressed sparse row format, duplicate entries are summed.
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        CsrMatrix::from_triplets(self.shape, self.triplets())
+    }
+
+    ///Matrix in compressed sparse column format, duplicate entries are summed.
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        CscMatrix::from_triplets(self.shape, self.triplets())
+    }
+
+    pub fn shape(&self) -> [usize; 2] {
+        self.shape
+    }
+
+    ///Number of stored elements, counting duplicates.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn rows(&self) -> &[usize] {
+        &self.rows
+    }
+
+    pub fn cols(&self) -> &[usize] {
+        &self.cols
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+#[cfg(test)]
+mod coo_test {
+    use super::*;
+
+    #[test]
+    fn assembly() {
+        let mut a = CooMatrix::new([3, 3]);
+        for i in 0..3 {
+            a.push(i, i, 2).unwrap();
+            a.push(i, (i + 1) % 3, -1).unwrap();
+            a.push((i + 1) % 3, i, -1).unwrap();
+        }
+        a.push(0, 0, 1).unwrap();
+        assert!(a.push(3, 0, 1).is_err());
+        assert_eq!(a.nnz(), 10);
+        let expected = Ndarr::from([[3, -1, -1], [-1, 2, -1], [-1, -1, 2]]);
+        assert_eq!(a.to_dense(), expected);
+        assert_eq!(a.to_csr().to_dense(), expected);
+        assert_eq!(a.to_csc().to_dense(), expected);
+        assert_eq!(a.to_csr().nnz(), 9);
+        let b = CooMatrix::from_triplets([2, 2], vec![1, 0], vec![0, 1], vec![5, 6]).unwrap();
+        assert_eq!(
+            (b.rows(), b.cols(), b.values()),
+            (&[1, 0][..], &[0, 1][..], &[5, 6][..])
+        );
+        assert_eq!(
+            CooMatrix::from_dense(&b.to_dense()).to_dense(),
+            b.to_dense()
+        );
+        assert!(CooMatrix::from_triplets([2, 2], vec![1], vec![0, 1], vec![5, 6]).is_err());
+        assert!(CooMatrix::from_triplets([2, 2], vec![2], vec![0], vec![5]).is_err());
+    }
+}
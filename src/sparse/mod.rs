@@ -0,0 +1,46 @@
+//!Sparse matrices, for operators that are mostly zeros like lattice Hamiltonians or graph Laplacians.
+//!`CooMatrix` is the coordinate format used to assemble a matrix element by element, `CsrMatrix` and `CscMatrix`
+//!are the compressed sparse row and column formats used for arithmetic and products.
+use super::*;
+use num_traits::Zero;
+use std::ops::Mul;
+
+mod compressed;
+mod coo;
+
+pub use compressed::{CscMatrix, CsrMatrix};
+pub use coo::CooMatrix;
+
+///Element types supported by the sparse matrices.
+pub trait SparseScalar:
+    Copy + Debug + PartialEq + Zero + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+}
+
+impl<T> SparseScalar for T where
+    T: Copy + Debug + PartialEq + Zero + Add<Output = T> + Sub<Output = T> + Mul<Output = T>
+{
+}
+
+//Checks that an index is inside a matrix of shape `shape`.
+fn check_index(shape: [usize; 2], i: usize, j: usize) -> Result<(), DimError> {
+    if i >= shape[0] || j >= shape[1] {
+        return Err(DimError::new(&format!(
+            "Index [{}, {}] is out of bounds for a sparse matrix of shape {:?}.",
+            i, j, shape
+        )));
+    }
+    Ok(())
+}
+
+//Checks that a dense array is a matrix or a vector with `n` rows, returns its number of columns.
+fn check_dense<T: Clone, R: Unsigned>(x: &Ndarr<T, R>, n: usize) -> Result<usize, DimError> {
+    let shape = x.shape();
+    if shape.is_empty() || shape.len() > 2 || shape[0] != n {
+        return Err(DimError::new(&format!(
+            "An array of shape {:?} can not multiply a sparse matrix with {} columns.",
+            shape, n
+        )));
+    }
+    Ok(if shape.len() == 2 { shape[1] } else { 1 })
+}
@@ -0,0 +1,525 @@
+use super::*;
+use num_traits::{Euclid, Num};
+use std::ops::{Add, Sub};
+use typenum::{Add1, Diff, Max, Maximum, Sub1, Sum, UTerm, B1};
+
+use crate::helpers::{multiply_list, nan_last_cmp};
+
+//Frame shape and trailing cells of an array.
+type Cells<T, K> = (Vec<usize>, Vec<Ndarr<T, K>>);
+//Rank of the result of applying a function returning rank J to the K-cells of an array of rank R.
+type CellsRank<R, K, J> = Sum<Diff<R, K>, J>;
+//Same for a dyadic function, where the frames of both arrays are broadcasted.
+type DyadicCellsRank<R1, K1, R2, K2, J> = Sum<Maximum<Diff<R1, K1>, Diff<R2, K2>>, J>;
+type DyadicCells<U, R1, K1, R2, K2, J> = Ndarr<U, DyadicCellsRank<R1, K1, R2, K2, J>>;
+
+impl<R: Unsigned> Ndarr<usize, R> {
+    ///APL iota (monadic `⍳`), an array of the given shape filled with the indexes `0, 1, 2, ...` in row-major order.
+    pub fn iota<D: Into<Dim<R>>>(shape: D) -> Self {
+        let dim = shape.into();
+        let n = multiply_list(&dim.shape, 1);
+        Ndarr {
+            data: (0..n).collect(),
+            dim,
+        }
+    }
+}
+
+impl<T: Clone + Debug, R: Unsigned> Ndarr<T, R> {
+    ///APL reshape (dyadic `⍴`), unlike `reshape` the elements are repeated cyclically until the new shape is filled,
+    ///or truncated if there are more elements than needed.
+    pub fn reshape_cyclic<R2: Unsigned, D: Into<Dim<R2>>>(
+        &self,
+        shape: D,
+    ) -> Result<Ndarr<T, R2>, DimError> {
+        let dim = shape.into();
+        let n = multiply_list(&dim.shape, 1);
+        if self.data.is_empty() && n > 0 {
+            return Err(DimError::new(&format!(
+                "Can not fill shape {:?} from an empty array.",
+                dim.shape
+            )));
+        }
+        let data = self.data.iter().cycle().take(n).cloned().collect();
+        Ok(Ndarr { data, dim })
+    }
+
+    ///APL membership (`∊`), returns an array with the same shape as `self` that is `true` where the element is found in `set`.
+    pub fn member_of<R2: Unsigned>(&self, set: &Ndarr<T, R2>) -> Ndarr<bool, R>
+    where
+        T: PartialEq,
+    {
+        self.map(|x| set.data.contains(x))
+    }
+
+    //Builds a new array by selecting, for every position `k < new_len` along `axis`, the source position `f(k)`
+    //along the same axis or the fill element `T::default()` if `f(k)` is `None`.
+    fn gather_axis<F: Fn(usize) -> Option<usize>>(
+        &self,
+        axis: usize,
+        new_len: usize,
+        f: F,
+    ) -> Result<Self, DimError>
+    where
+        T: Default,
+    {
+        if axis >= self.rank() {
+            return Err(DimError::new("Axis grater than rank"));
+        }
+        let shape = self.shape();
+        let len = shape[axis];
+        let outer = multiply_list(&shape[..axis], 1);
+        let inner = multiply_list(&shape[axis + 1..], 1);
+        let mut data = Vec::with_capacity(outer * new_len * inner);
+        for o in 0..outer {
+            for k in 0..new_len {
+                match f(k) {
+                    Some(s) => {
+                        let start = (o * len + s) * inner;
+                        data.extend_from_slice(&self.data[start..start + inner]);
+                    }
+                    None => data.extend(std::iter::repeat_n(T::default(), inner)),
+                }
+            }
+        }
+        let mut new_shape = shape.to_vec();
+        new_shape[axis] = new_len;
+        Ok(Ndarr {
+            data,
+            dim: Dim::new(&new_shape)?,
+        })
+    }
+
+    ///APL take (`↑`) along an axis. A positive `n` takes the first `n` elements and a negative `n` the last `|n|`.
+    ///If `|n|` is greater than the length of the axis the result is padded with `T::default()`.
+    pub fn take(&self, n: isize, axis: usize) -> Result<Self, DimError>
+    where
+        T: Default,
+    {
+        let len = *self.shape().get(axis).unwrap_or(&0) as isize;
+        let m = n.unsigned_abs();
+        if n >= 0 {
+            self.gather_axis(axis, m, |k| if k < len as usize { Some(k) } else { None })
+        } else {
+            //position k of the output corresponds to k - (|n| - len) of the input
+            let offset = m as isize - len;
+            self.gather_axis(axis, m, |k| {
+                let s = k as isize - offset;
+                if s >= 0 {
+                    Some(s as usize)
+                } else {
+                    None
+                }
+            })
+        }
+    }
+
+    ///APL drop (`↓`) along an axis. A positive `n` drops the first `n` elements and a negative `n` the last `|n|`.
+    pub fn drop(&self, n: isize, axis: usize) -> Result<Self, DimError>
+    where
+        T: Default,
+    {
+        let len = *self.shape().get(axis).unwrap_or(&0);
+        let m = n.unsigned_abs().min(len);
+        if n >= 0 {
+            self.gather_axis(axis, len - m, |k| Some(k + m))
+        } else {
+            self.gather_axis(axis, len - m, Some)
+        }
+    }
+
+    ///APL expand (`\`) along an axis. `mask` must have as many `true` values as the length of the axis,
+    ///the elements are placed in the `true` positions and `T::default()` is inserted in the `false` ones.
+    pub fn expand(&self, mask: &Ndarr<bool, U1>, axis: usize) -> Result<Self, DimError>
+    where
+        T: Default,
+    {
+        let len = *self.shape().get(axis).unwrap_or(&0);
+        let n_true = mask.data.iter().filter(|b| **b).count();
+        if n_true != len {
+            return Err(DimError::new(&format!(
+                "Expand mask has {} true values but the axis has length {}.",
+                n_true, len
+            )));
+        }
+        let mut positions = Vec::with_capacity(mask.len());
+        let mut next = 0;
+        for b in mask.data.iter() {
+            if *b {
+                positions.push(Some(next));
+                next += 1;
+            } else {
+                positions.push(None);
+            }
+        }
+        self.gather_axis(axis, mask.len(), |k| positions[k])
+    }
+
+    ///APL first along an axis, the first slice of the array along the specified axis.
+    pub fn first(&self, axis: usize) -> Result<Ndarr<T, Sub1<R>>, DimError>
+    where
+        R: Sub<B1>,
+        <R as Sub<B1>>::Output: Unsigned,
+    {
+        if axis >= self.rank() {
+            return Err(DimError::new("Axis grater than rank"));
+        } else if self.shape()[axis] == 0 {
+            return Err(DimError::new(
+                "Can not take the first slice of an empty axis",
+            ));
+        }
+        let slice = self.index_slice_notyped(axis, 0);
+        Ok(Ndarr {
+            data: slice.data,
+            dim: Dim::new(&slice.dim.shape)?,
+        })
+    }
+}
+
+impl<T: Clone + Debug> Ndarr<T, U1> {
+    ///APL index of (dyadic `⍳`), for each element of `values` returns the index of its first occurrence in `self`,
+    ///or `self.len()` if it is not found.
+    pub fn index_of<R2: Unsigned>(&self, values: &Ndarr<T, R2>) -> Ndarr<usize, R2>
+    where
+        T: PartialEq,
+    {
+        values.map(|v| self.data.iter().position(|x| x == v).unwrap_or(self.len()))
+    }
+
+    ///APL grade up (`⍋`), the permutation of indexes that sorts the array in ascending order. Equal elements keep their relative order
+    ///and NaN sorts last.
+    pub fn grade_up(&self) -> Ndarr<usize, U1>
+    where
+        T: PartialOrd,
+    {
+        let mut indexes: Vec<usize> = (0..self.len()).collect();
+        indexes.sort_by(|a, b| nan_last_cmp(&self.data[*a], &self.data[*b]));
+        Ndarr::from(indexes)
+    }
+
+    ///APL grade down (`⍒`), the permutation of indexes that sorts the array in descending order. Equal elements keep their relative order
+    ///and NaN sorts first.
+    pub fn grade_down(&self) -> Ndarr<usize, U1>
+    where
+        T: PartialOrd,
+    {
+        let mut indexes: Vec<usize> = (0..self.len()).collect();
+        indexes.sort_by(|a, b| nan_last_cmp(&self.data[*b], &self.data[*a]));
+        Ndarr::from(indexes)
+    }
+}
+
+impl<T: Clone + Debug + Copy + Num + Euclid, R: Unsigned> Ndarr<T, R> {
+    ///APL encode (`⊤`), represents each element in the mixed radix number system `radix`.
+    ///The digits are placed along a new leading axis, e.g. encoding with `[24, 60, 60]` converts seconds to hours, minutes and seconds.
+    ///A radix of zero keeps the remaining value as the leading digit.
+    pub fn encode(&self, radix: &Ndarr<T, U1>) -> Ndarr<T, Add1<R>>
+    where
+        R: Add<B1>,
+        <R as Add<B1>>::Output: Unsigned,
+    {
+        let n = self.len();
+        let r = radix.len();
+        let mut data = vec![T::zero(); n * r];
+        for (i, x) in self.data.iter().enumerate() {
+            let mut value = *x;
+            for k in (0..r).rev() {
+                let base = radix.data[k];
+                if base.is_zero() {
+                    data[k * n + i] = value;
+                    value = T::zero();
+                } else {
+                    data[k * n + i] = value.rem_euclid(&base);
+                    value = value.div_euclid(&base);
+                }
+            }
+        }
+        Ndarr {
+            data,
+            dim: self.dim.clone().insert_element(0, r),
+        }
+    }
+
+    ///APL decode (`⊥`), evaluates the digits along the leading axis in the mixed radix number system `radix`.
+    ///This is the inverse of `encode`.
+    pub fn decode(&self, radix: &Ndarr<T, U1>) -> Result<Ndarr<T, Sub1<R>>, DimError>
+    where
+        R: Sub<B1>,
+        <R as Sub<B1>>::Output: Unsigned,
+    {
+        if self.rank() == 0 || self.shape()[0] != radix.len() {
+            return Err(DimError::new(&format!(
+                "Can not decode array of shape {:?} with radix of length {}.",
+                self.shape(),
+                radix.len()
+            )));
+        }
+        let new_dim = self.dim.clone().remove_element(0);
+        let n = multiply_list(&new_dim.shape, 1);
+        let mut data = vec![T::zero(); n];
+        for (k, base) in radix.data.iter().enumerate() {
+            for (i, acc) in data.iter_mut().enumerate() {
+                *acc = *acc * *base + self.data[k * n + i];
+            }
+        }
+        Ok(Ndarr { data, dim: new_dim })
+    }
+}
+
+impl<T: Clone + Debug, R: Unsigned> Ndarr<T, R> {
+    //Splits the array into its frame shape and the list of trailing k-cells.
+    fn cells<K: Unsigned>(&self) -> Result<Cells<T, K>, DimError> {
+        let k = K::to_usize();
+        let r = self.rank();
+        if k > r {
+            return Err(DimError::new(&format!(
+                "Can not take cells of rank {} from an array of rank {}.",
+                k, r
+            )));
+        }
+        let frame = self.shape()[..r - k].to_vec();
+        let cell_dim = Dim::<K>::new(&self.shape()[r - k..])?;
+        let cell_size = multiply_list(&cell_dim.shape, 1);
+        let n_cells = multiply_list(&frame, 1);
+        let cells = (0..n_cells)
+            .map(|i| Ndarr {
+                data: self.data[i * cell_size..(i + 1) * cell_size].to_vec(),
+                dim: cell_dim.clone(),
+            })
+            .collect();
+        Ok((frame, cells))
+    }
+
+    ///APL rank operator (`⍤`), applies `f` to every k-cell of the array, i.e. the sub-arrays formed by the last `K` axes,
+    ///and assembles the results in the frame formed by the remaining leading axes.
+    ///All the results of `f` must have the same shape.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1, 2, 3], [4, 5, 6]]);
+    ///let rolled = a.apply_cells(U1::new(), |row: Ndarr<i32, U1>| row.roll(1, 0));
+    ///assert_eq!(rolled.unwrap(), Ndarr::from([[3, 1, 2], [6, 4, 5]]));
+    ///```
+    pub fn apply_cells<K, U, J, F>(
+        &self,
+        _k: K,
+        f: F,
+    ) -> Result<Ndarr<U, CellsRank<R, K, J>>, DimError>
+    where
+        K: Unsigned,
+        J: Unsigned,
+        U: Clone + Debug,
+        R: Sub<K>,
+        Diff<R, K>: Add<J>,
+        CellsRank<R, K, J>: Unsigned,
+        F: Fn(Ndarr<T, K>) -> Ndarr<U, J>,
+    {
+        let (frame, cells) = self.cells::<K>()?;
+        let results = cells.into_iter().map(f).collect();
+        Self::assemble_cells(&frame, results)
+    }
+
+    ///Dyadic version of `apply_cells`, pairs the `K1`-cells of `self` with the `K2`-cells of `other` and applies `f` to each pair.
+    ///The frames of both arrays are broadcasted with the usual broadcasting rules.
+    pub fn apply_cells_dyadic<K1, K2, T2, R2, U, J, F>(
+        &self,
+        other: &Ndarr<T2, R2>,
+        _k1: K1,
+        _k2: K2,
+        f: F,
+    ) -> Result<DyadicCells<U, R, K1, R2, K2, J>, DimError>
+    where
+        K1: Unsigned,
+        K2: Unsigned,
+        J: Unsigned,
+        T2: Clone + Debug,
+        R2: Unsigned + Sub<K2>,
+        U: Clone + Debug,
+        R: Sub<K1>,
+        Diff<R, K1>: Max<Diff<R2, K2>>,
+        Maximum<Diff<R, K1>, Diff<R2, K2>>: Add<J>,
+        DyadicCellsRank<R, K1, R2, K2, J>: Unsigned,
+        F: Fn(Ndarr<T, K1>, Ndarr<T2, K2>) -> Ndarr<U, J>,
+    {
+        let (frame1, cells1) = self.cells::<K1>()?;
+        let (frame2, cells2) = other.cells::<K2>()?;
+        let frame1 = Dim::<UTerm>::new(&frame1)?;
+        let frame2 = Dim::<UTerm>::new(&frame2)?;
+        let frame = frame1.broadcast_shape_notyped(&frame2)?;
+        let n = multiply_list(&frame.shape, 1);
+        let mut results = Vec::with_capacity(n);
+        for i in 0..n {
+            let indexes = frame.get_indexes(&i);
+            let p1 = Dim::<UTerm>::rev_cast_pos(&frame1, &indexes)?;
+            let p2 = Dim::<UTerm>::rev_cast_pos(&frame2, &indexes)?;
+            results.push(f(cells1[p1].clone(), cells2[p2].clone()));
+        }
+        Self::assemble_cells(&frame.shape, results)
+    }
+
+    //Concatenates the results of a rank operator into an array of shape frame ++ cell shape.
+    fn assemble_cells<U: Clone + Debug, J: Unsigned, R2: Unsigned>(
+        frame: &[usize],
+        results: Vec<Ndarr<U, J>>,
+    ) -> Result<Ndarr<U, R2>, DimError> {
+        let cell_shape = match results.first() {
+            Some(first) => first.shape().to_vec(),
+            None => vec![0; J::to_usize()],
+        };
+        let mut data = Vec::with_capacity(results.len() * multiply_list(&cell_shape, 1));
+        for res in results {
+            if res.shape() != cell_shape.as_slice() {
+                return Err(DimError::new(&format!(
+                    "Results with shapes {:?} and {:?} can not be assembled.",
+                    cell_shape,
+                    res.shape()
+                )));
+            }
+            data.extend(res.data);
+        }
+        let mut shape = frame.to_vec();
+        shape.extend(cell_shape);
+        Ok(Ndarr {
+            data,
+            dim: Dim::new(&shape)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod apl_test {
+    use super::*;
+
+    #[test]
+    fn iota() {
+        assert_eq!(Ndarr::iota([4]), Ndarr::from([0, 1, 2, 3]));
+        assert_eq!(Ndarr::iota([2, 2]), Ndarr::from([[0, 1], [2, 3]]));
+    }
+
+    #[test]
+    fn reshape_cyclic() {
+        let a = Ndarr::from([1, 2, 3]);
+        assert_eq!(
+            a.reshape_cyclic([2, 4]).unwrap(),
+            Ndarr::from([[1, 2, 3, 1], [2, 3, 1, 2]])
+        );
+        assert_eq!(a.reshape_cyclic([2]).unwrap(), Ndarr::from([1, 2]));
+    }
+
+    #[test]
+    fn index_member() {
+        let a = Ndarr::from("hello");
+        assert_eq!(a.index_of(&Ndarr::from("lox")), Ndarr::from([2, 4, 5]));
+        assert_eq!(
+            Ndarr::from([[1, 2], [3, 4]]).member_of(&Ndarr::from([4, 1, 7])),
+            Ndarr::from([[true, false], [false, true]])
+        );
+    }
+
+    #[test]
+    fn grade() {
+        let a = Ndarr::from([3., 1., 2., 1.]);
+        assert_eq!(a.grade_up(), Ndarr::from([1, 3, 2, 0]));
+        assert_eq!(a.grade_down(), Ndarr::from([0, 2, 1, 3]));
+
+        //large enough for the sort to detect an inconsistent comparator
+        let mut data: Vec<f64> = (0..64).map(|i| ((i * 37) % 64) as f64).collect();
+        data[5] = f64::NAN;
+        data[40] = f64::NAN;
+        let a = Ndarr::from(data);
+        let up = a.grade_up();
+        assert_eq!(up.data[62..], [5, 40]);
+        assert!(up.data[..62]
+            .windows(2)
+            .all(|w| a.data[w[0]] <= a.data[w[1]]));
+        let down = a.grade_down();
+        assert_eq!(down.data[..2], [5, 40]);
+        assert!(down.data[2..]
+            .windows(2)
+            .all(|w| a.data[w[0]] >= a.data[w[1]]));
+    }
+
+    #[test]
+    fn encode_decode() {
+        let radix = Ndarr::from([24, 60, 60]);
+        let seconds = Ndarr::from([3723, 86399]);
+        let hms = seconds.encode(&radix);
+        assert_eq!(hms, Ndarr::from([[1, 23], [2, 59], [3, 59]]));
+        assert_eq!(hms.decode(&radix).unwrap(), seconds);
+        let bits = Ndarr::from([5]).encode(&Ndarr::from([0, 2, 2]));
+        assert_eq!(bits, Ndarr::from([[1], [0], [1]]));
+        assert!(Ndarr::from([1, 0]).decode(&radix).is_err());
+    }
+
+    #[test]
+    fn take_drop() {
+        let a = Ndarr::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(a.take(2, 1).unwrap(), Ndarr::from([[1, 2], [4, 5]]));
+        assert_eq!(a.take(-1, 0).unwrap(), Ndarr::from([[4, 5, 6]]));
+        assert_eq!(
+            a.take(-4, 1).unwrap(),
+            Ndarr::from([[0, 1, 2, 3], [0, 4, 5, 6]])
+        );
+        assert_eq!(a.drop(1, 1).unwrap(), Ndarr::from([[2, 3], [5, 6]]));
+        assert_eq!(a.drop(-1, 0).unwrap(), Ndarr::from([[1, 2, 3]]));
+        assert_eq!(a.drop(5, 0).unwrap().shape(), &[0, 3]);
+        assert!(a.take(1, 2).is_err());
+    }
+
+    #[test]
+    fn expand_first() {
+        let a = Ndarr::from([[1, 2], [3, 4]]);
+        let mask = Ndarr::from([true, false, true]);
+        assert_eq!(
+            a.expand(&mask, 1).unwrap(),
+            Ndarr::from([[1, 0, 2], [3, 0, 4]])
+        );
+        assert!(a.expand(&Ndarr::from([true]), 0).is_err());
+        assert_eq!(a.first(0).unwrap(), Ndarr::from([1, 2]));
+        assert_eq!(a.first(1).unwrap(), Ndarr::from([1, 3]));
+    }
+
+    #[test]
+    fn apply_cells() {
+        let a = Ndarr::from([[1, 2, 3], [4, 5, 6]]);
+        let sums = a
+            .apply_cells(U1::new(), |row: Ndarr<i32, U1>| {
+                Ndarr::from([row.data.iter().sum::<i32>()])
+            })
+            .unwrap();
+        assert_eq!(sums, Ndarr::from([[6], [15]]));
+        let t = a.apply_cells(U2::new(), |m: Ndarr<i32, U2>| m.t()).unwrap();
+        assert_eq!(t, a.t());
+        let scaled = a
+            .apply_cells(U0::new(), |x: Ndarr<i32, U0>| Ndarr::from([x.data[0]; 2]))
+            .unwrap();
+        assert_eq!(scaled.shape(), &[2, 3, 2]);
+    }
+
+    #[test]
+    fn apply_cells_dyadic() {
+        //add a vector to each row of a stack of matrices
+        let a = Ndarr::from([[[1, 2], [3, 4]], [[5, 6], [7, 8]]]);
+        let v = Ndarr::from([[10, 20], [30, 40]]);
+        let res = a
+            .apply_cells_dyadic(
+                &v,
+                U1::new(),
+                U1::new(),
+                |x: Ndarr<i32, U1>, y: Ndarr<i32, U1>| x + y,
+            )
+            .unwrap();
+        assert_eq!(
+            res,
+            Ndarr::from([[[11, 22], [33, 44]], [[15, 26], [37, 48]]])
+        );
+        let bad = Ndarr::from([[1, 2], [3, 4], [5, 6]]);
+        assert!(a
+            .apply_cells_dyadic(
+                &bad,
+                U1::new(),
+                U1::new(),
+                |x: Ndarr<i32, U1>, y: Ndarr<i32, U1>| x + y
+            )
+            .is_err());
+    }
+}
@@ -32,3 +32,19 @@ where
         Ndarr { data, dim: shape }
     }
 }
+
+impl<T: Clone, R: Unsigned> Ndarr<T, R> {
+    ///Array of the given shape whose elements are `f` evaluated at their indexes, in row-major order.
+    ///```
+    ///use rapl::*;
+    ///let a: Ndarr<usize, U2> = Ndarr::from_fn([2, 3], |idx: Dim<U2>| 10 * idx.shape[0] + idx.shape[1]);
+    ///assert_eq!(a, Ndarr::from([[0, 1, 2], [10, 11, 12]]));
+    ///```
+    pub fn from_fn<D: Into<Dim<R>>, F: FnMut(Dim<R>) -> T>(shape: D, mut f: F) -> Self {
+        let dim = shape.into();
+        let data = (0..dim.get_number_elements())
+            .map(|i| f(dim.get_indexes(&i)))
+            .collect();
+        Ndarr { data, dim }
+    }
+}
@@ -0,0 +1,303 @@
+use num_traits::{Float, One, Zero};
+use std::ops::Neg;
+
+use super::*;
+
+//Position of the element `[i, j]` relative to the diagonal `k`, zero on it and positive above it.
+fn offset(i: usize, j: usize, k: isize) -> isize {
+    j as isize - i as isize - k
+}
+
+impl<T: Clone + Debug> Ndarr<T, U2> {
+    ///`n x m` matrix with ones on the diagonal `k` and zeros elsewhere. `k = 0` is the main diagonal, positive `k` are
+    ///above it and negative below it.
+    ///```
+    ///use rapl::*;
+    ///assert_eq!(Ndarr::eye(2, 3, 1), Ndarr::from([[0, 1, 0], [0, 0, 1]]));
+    ///```
+    pub fn eye(n: usize, m: usize, k: isize) -> Self
+    where
+        T: Zero + One,
+    {
+        Ndarr::from_fn([n, m], |idx: Dim<U2>| {
+            if offset(idx.shape[0], idx.shape[1], k) == 0 {
+                T::one()
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    ///`n x n` identity matrix.
+    pub fn identity(n: usize) -> Self
+    where
+        T: Zero + One,
+    {
+        Ndarr::eye(n, n, 0)
+    }
+
+    ///Square matrix with the elements of `v` on the diagonal `k` and zeros elsewhere.
+    ///```
+    ///use rapl::*;
+    ///let v = Ndarr::from([1, 2]);
+    ///assert_eq!(Ndarr::diag(&v, -1), Ndarr::from([[0, 0, 0], [1, 0, 0], [0, 2, 0]]));
+    ///```
+    pub fn diag(v: &Ndarr<T, U1>, k: isize) -> Self
+    where
+        T: Zero,
+    {
+        let n = v.len() + k.unsigned_abs();
+        Ndarr::from_fn([n, n], |idx: Dim<U2>| {
+            let (i, j) = (idx.shape[0], idx.shape[1]);
+            if offset(i, j, k) == 0 {
+                v.data[i.min(j)].clone()
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    ///Elements of the diagonal `k`, empty if the diagonal is outside the matrix.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1, 2, 3], [4, 5, 6]]);
+    ///assert_eq!(a.diagonal(0), Ndarr::from([1, 5]));
+    ///assert_eq!(a.diagonal(1), Ndarr::from([2, 6]));
+    ///```
+    pub fn diagonal(&self, k: isize) -> Ndarr<T, U1> {
+        let (n, m) = (self.shape()[0], self.shape()[1]);
+        let (i0, j0) = if k >= 0 {
+            (0, k as usize)
+        } else {
+            (k.unsigned_abs(), 0)
+        };
+        let len = n.saturating_sub(i0).min(m.saturating_sub(j0));
+        let data: Vec<T> = (0..len)
+            .map(|d| self.data[(i0 + d) * m + j0 + d].clone())
+            .collect();
+        Ndarr {
+            dim: Dim::new(&[len]).unwrap(),
+            data,
+        }
+    }
+
+    ///Sum of the main diagonal.
+    ///```
+    ///use rapl::*;
+    ///assert_eq!(Ndarr::from([[1, 2], [3, 4]]).trace(), 5);
+    ///```
+    pub fn trace(&self) -> T
+    where
+        T: Zero,
+    {
+        self.diagonal(0)
+            .data
+            .into_iter()
+            .fold(T::zero(), |s, x| s + x)
+    }
+
+    ///Upper triangle of the matrix, the elements below the diagonal `k` are set to zero.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+    ///assert_eq!(a.triu(0), Ndarr::from([[1, 2, 3], [0, 5, 6], [0, 0, 9]]));
+    ///assert_eq!(a.tril(-1), Ndarr::from([[0, 0, 0], [4, 0, 0], [7, 8, 0]]));
+    ///```
+    pub fn triu(&self, k: isize) -> Self
+    where
+        T: Zero,
+    {
+        let m = self.shape()[1];
+        Ndarr::from_fn(self.dim.clone(), |idx: Dim<U2>| {
+            let (i, j) = (idx.shape[0], idx.shape[1]);
+            if offset(i, j, k) >= 0 {
+                self.data[i * m + j].clone()
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    ///Lower triangle of the matrix, the elements above the diagonal `k` are set to zero.
+    pub fn tril(&self, k: isize) -> Self
+    where
+        T: Zero,
+    {
+        let m = self.shape()[1];
+        Ndarr::from_fn(self.dim.clone(), |idx: Dim<U2>| {
+            let (i, j) = (idx.shape[0], idx.shape[1]);
+            if offset(i, j, k) <= 0 {
+                self.data[i * m + j].clone()
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    ///Toeplitz matrix, constant along its diagonals, with first column `c` and first row `r`.
+    ///The first element of `r` is ignored.
+    ///```
+    ///use rapl::*;
+    ///let t = Ndarr::toeplitz(&Ndarr::from([1, 2, 3]), &Ndarr::from([1, 4]));
+    ///assert_eq!(t, Ndarr::from([[1, 4], [2, 1], [3, 2]]));
+    ///```
+    pub fn toeplitz(c: &Ndarr<T, U1>, r: &Ndarr<T, U1>) -> Self {
+        Ndarr::from_fn([c.len(), r.len()], |idx: Dim<U2>| {
+            let (i, j) = (idx.shape[0], idx.shape[1]);
+            if i >= j {
+                c.data[i - j].clone()
+            } else {
+                r.data[j - i].clone()
+            }
+        })
+    }
+
+    ///Circulant matrix with first column `c`, every column is the previous one rotated down by one.
+    ///```
+    ///use rapl::*;
+    ///let c = Ndarr::circulant(&Ndarr::from([1, 2, 3]));
+    ///assert_eq!(c, Ndarr::from([[1, 3, 2], [2, 1, 3], [3, 2, 1]]));
+    ///```
+    pub fn circulant(c: &Ndarr<T, U1>) -> Self {
+        let n = c.len();
+        Ndarr::from_fn([n, n], |idx: Dim<U2>| {
+            c.data[(n + idx.shape[0] - idx.shape[1]) % n].clone()
+        })
+    }
+
+    ///Hankel matrix, constant along its anti-diagonals, with first column `c` and last row `r`.
+    ///The first element of `r` is ignored.
+    ///```
+    ///use rapl::*;
+    ///let h = Ndarr::hankel(&Ndarr::from([1, 2, 3]), &Ndarr::from([3, 4, 5]));
+    ///assert_eq!(h, Ndarr::from([[1, 2, 3], [2, 3, 4], [3, 4, 5]]));
+    ///```
+    pub fn hankel(c: &Ndarr<T, U1>, r: &Ndarr<T, U1>) -> Self {
+        let n = c.len();
+        Ndarr::from_fn([n, r.len()], |idx: Dim<U2>| {
+            let s = idx.shape[0] + idx.shape[1];
+            if s < n {
+                c.data[s].clone()
+            } else {
+                r.data[s + 1 - n].clone()
+            }
+        })
+    }
+
+    ///Vandermonde matrix with `n` columns, the powers of `x` from `x^(n - 1)` down to `x^0`, or the other way around
+    ///if `increasing`.
+    ///```
+    ///use rapl::*;
+    ///let v = Ndarr::vander(&Ndarr::from([1, 2, 3]), 3, false);
+    ///assert_eq!(v, Ndarr::from([[1, 1, 1], [4, 2, 1], [9, 3, 1]]));
+    ///```
+    pub fn vander(x: &Ndarr<T, U1>, n: usize, increasing: bool) -> Self
+    where
+        T: One,
+    {
+        Ndarr::from_fn([x.len(), n], |idx: Dim<U2>| {
+            let p = if increasing {
+                idx.shape[1]
+            } else {
+                n - 1 - idx.shape[1]
+            };
+            (0..p).fold(T::one(), |acc, _| acc * x.data[idx.shape[0]].clone())
+        })
+    }
+
+    ///`n x n` Hilbert matrix, with elements `1 / (i + j + 1)`. A classic example of an ill conditioned matrix.
+    ///```
+    ///use rapl::*;
+    ///assert_eq!(Ndarr::hilbert(2), Ndarr::from([[1., 0.5], [0.5, 1. / 3.]]));
+    ///```
+    pub fn hilbert(n: usize) -> Self
+    where
+        T: Float,
+    {
+        Ndarr::from_fn([n, n], |idx: Dim<U2>| {
+            T::one() / T::from(idx.shape[0] + idx.shape[1] + 1).unwrap()
+        })
+    }
+
+    ///`n x n` Hadamard matrix of Sylvester's construction, with orthogonal rows of `1` and `-1`.
+    ///Returns Error if `n` is not a power of 2.
+    ///```
+    ///use rapl::*;
+    ///assert_eq!(Ndarr::hadamard(2).unwrap(), Ndarr::from([[1, 1], [1, -1]]));
+    ///assert!(Ndarr::<i32, U2>::hadamard(3).is_err());
+    ///```
+    pub fn hadamard(n: usize) -> Result<Self, DimError>
+    where
+        T: One + Neg<Output = T>,
+    {
+        if !n.is_power_of_two() {
+            return Err(DimError::new(&format!(
+                "The order of a Hadamard matrix must be a power of 2, found {}.",
+                n
+            )));
+        }
+        Ok(Ndarr::from_fn([n, n], |idx: Dim<U2>| {
+            if (idx.shape[0] & idx.shape[1]).count_ones().is_multiple_of(2) {
+                T::one()
+            } else {
+                -T::one()
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod matrices_test {
+    use super::*;
+
+    #[test]
+    fn from_fn() {
+        let a: Ndarr<usize, U3> = Ndarr::from_fn([2, 2, 2], |idx: Dim<U3>| idx.shape.iter().sum());
+        assert_eq!(a, Ndarr::from([[[0, 1], [1, 2]], [[1, 2], [2, 3]]]));
+        let empty: Ndarr<i32, U2> = Ndarr::from_fn([0, 3], |_: Dim<U2>| 1);
+        assert_eq!(empty.shape(), &[0, 3]);
+    }
+
+    #[test]
+    fn diagonals() {
+        assert_eq!(Ndarr::<i32, U2>::identity(2), Ndarr::from([[1, 0], [0, 1]]));
+        assert_eq!(Ndarr::eye(3, 2, -1), Ndarr::from([[0, 0], [1, 0], [0, 1]]));
+        assert_eq!(
+            Ndarr::<i32, U2>::eye(2, 2, 5),
+            Ndarr::from([[0, 0], [0, 0]])
+        );
+        let v = Ndarr::from([1, 2]);
+        let d = Ndarr::diag(&v, 1);
+        assert_eq!(d, Ndarr::from([[0, 1, 0], [0, 0, 2], [0, 0, 0]]));
+        assert_eq!(d.diagonal(1), v);
+        assert_eq!(Ndarr::diag(&v, 0).diagonal(0), v);
+        let a = Ndarr::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(a.diagonal(-1), Ndarr::from([4]));
+        assert_eq!(a.diagonal(3).shape(), &[0]);
+        assert_eq!(a.diagonal(-2).shape(), &[0]);
+        assert_eq!(a.trace(), 6);
+        assert_eq!(a.triu(1), Ndarr::from([[0, 2, 3], [0, 0, 6]]));
+        assert_eq!(a.tril(0), Ndarr::from([[1, 0, 0], [4, 5, 0]]));
+        assert_eq!(a.triu(0) + a.tril(-1), a);
+    }
+
+    #[test]
+    fn structured() {
+        let c = Ndarr::from([1, 2, 3]);
+        let t = Ndarr::toeplitz(&c, &c);
+        assert_eq!(t, t.t());
+        assert_eq!(Ndarr::circulant(&c).diagonal(-1), Ndarr::from([2, 2]));
+        let h = Ndarr::hankel(&c, &Ndarr::from([0, 7]));
+        assert_eq!(h, Ndarr::from([[1, 2], [2, 3], [3, 7]]));
+        assert_eq!(
+            Ndarr::vander(&Ndarr::from([2, 3]), 4, true),
+            Ndarr::from([[1, 2, 4, 8], [1, 3, 9, 27]])
+        );
+        let hil: Ndarr<f64, U2> = Ndarr::hilbert(3);
+        assert_eq!(hil, hil.t());
+        assert_eq!(hil.data[8], 0.2);
+        let had: Ndarr<i32, U2> = Ndarr::hadamard(8).unwrap();
+        assert_eq!(had.mat_mul(&had.t()), Ndarr::<i32, U2>::identity(8) * 8);
+        assert!(Ndarr::<f64, U2>::hadamard(0).is_err());
+    }
+}
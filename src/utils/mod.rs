@@ -2,8 +2,12 @@ use super::Ndarr;
 use super::*;
 
 mod activation;
+mod apl;
 mod fill_arr;
+mod matrices;
 mod spaces;
+mod special;
+mod windows;
 
 pub mod random;
 
@@ -150,20 +150,26 @@ impl Ndarr<f32, U2> {
 mod image_test {
     use super::*;
     use crate::de_slice;
+
+    //test outputs go to the temporary directory, the images in `graphics` are tracked
+    fn out_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(name)
+    }
+
     #[test]
     fn open_rgb8() {
         let img = open_rgbu8("graphics/test_img.jpg").unwrap();
         let mut slices = img.slice_at(2);
         slices[2].map_in_place(|x| x.wrapping_add(200));
         let des = de_slice(&slices, 2);
-        des.save_as_rgb("graphics/out_blue.png", ImageFormat::Png);
+        des.save_as_rgb(out_path("rapl_out_blue.png"), ImageFormat::Png);
     }
     #[test]
     fn open_f32() {
         let img = open_lumaf32("graphics/test_img.jpg").unwrap();
-        img.save_as_luma("graphics/out_test_bw.jpg", ImageFormat::Png);
+        img.save_as_luma(out_path("rapl_out_test_bw.png"), ImageFormat::Png);
         //square image
         let square = &img * &img;
-        square.save_as_luma("graphics/out_test_bw_square.jpg", ImageFormat::Png);
+        square.save_as_luma(out_path("rapl_out_test_bw_square.png"), ImageFormat::Png);
     }
 }
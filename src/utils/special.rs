@@ -0,0 +1,790 @@
+use num_traits::Float;
+use std::f64::consts::{FRAC_2_SQRT_PI, PI};
+
+use super::*;
+
+const EPS: f64 = f64::EPSILON;
+//Cap on the number of terms of every series, continued fraction and quadrature below.
+const MAX_ITER: usize = 1000;
+const FPMIN: f64 = 1e-300;
+const EULER_GAMMA: f64 = 0.5772156649015329;
+
+//Lanczos approximation coefficients with g = 7, n = 9.
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS: [f64; 9] = [
+    0.9999999999998099,
+    676.5203681218851,
+    -1259.1392167224028,
+    771.3234287776531,
+    -176.6150291621406,
+    12.507343278686905,
+    -0.13857109526572012,
+    9.984369578019572e-06,
+    1.5056327351493116e-07,
+];
+
+fn lanczos_sum(x: f64) -> f64 {
+    let mut a = LANCZOS[0];
+    for (i, c) in LANCZOS.iter().enumerate().skip(1) {
+        a += c / (x + i as f64);
+    }
+    a
+}
+
+fn gamma(x: f64) -> f64 {
+    if x <= 0.0 && x.fract() == 0.0 {
+        //poles at zero and the negative integers, sin(πx) is only rounding noise there
+        f64::NAN
+    } else if x > 171.7 {
+        //Γ(x) overflows from x ≈ 171.62
+        f64::INFINITY
+    } else if x < 0.5 {
+        //reflection formula Γ(x)Γ(1-x) = π/sin(πx)
+        PI / ((PI * x).sin() * gamma(1.0 - x))
+    } else {
+        let x = x - 1.0;
+        let t = x + LANCZOS_G + 0.5;
+        //split the power in two to avoid overflowing before multiplying by exp(-t)
+        let half = t.powf((x + 0.5) / 2.0);
+        (2.0 * PI).sqrt() * half * (-t).exp() * half * lanczos_sum(x)
+    }
+}
+
+fn lgamma(x: f64) -> f64 {
+    if x <= 0.0 && x.fract() == 0.0 {
+        f64::NAN
+    } else if x == f64::INFINITY {
+        x
+    } else if x < 0.5 {
+        (PI / (PI * x).sin().abs()).ln() - lgamma(1.0 - x)
+    } else {
+        let x = x - 1.0;
+        let t = x + LANCZOS_G + 0.5;
+        0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + lanczos_sum(x).ln()
+    }
+}
+
+fn digamma(x: f64) -> f64 {
+    if x <= 0.0 && x == x.floor() {
+        return f64::NAN;
+    }
+    let mut x = x;
+    let mut result = 0.0;
+    if x < 0.0 {
+        //reflection formula ψ(1-x) - ψ(x) = π cot(πx)
+        result -= PI / (PI * x).tan();
+        x = 1.0 - x;
+    }
+    //recurrence ψ(x+1) = ψ(x) + 1/x until the asymptotic series is accurate
+    while x < 10.0 {
+        result -= 1.0 / x;
+        x += 1.0;
+    }
+    let f = 1.0 / (x * x);
+    let series = f
+        * (1.0 / 12.0
+            - f * (1.0 / 120.0
+                - f * (1.0 / 252.0
+                    - f * (1.0 / 240.0 - f * (1.0 / 132.0 - f * (691.0 / 32760.0 - f / 12.0))))));
+    result + x.ln() - 0.5 / x - series
+}
+
+//Regularized lower incomplete gamma P(a,x) by its series representation, valid for x < a + 1.
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let front = (-x + a * x.ln() - lgamma(a)).exp();
+    if front == 0.0 {
+        return 0.0;
+    }
+    let mut ap = a;
+    let mut del = 1.0 / a;
+    let mut sum = del;
+    for _ in 0..MAX_ITER {
+        ap += 1.0;
+        del *= x / ap;
+        sum += del;
+        if del.abs() <= sum.abs() * EPS {
+            break;
+        }
+    }
+    sum * front
+}
+
+//Regularized upper incomplete gamma Q(a,x) by its continued fraction representation, valid for x >= a + 1.
+fn gamma_cont_frac(a: f64, x: f64) -> f64 {
+    let front = (-x + a * x.ln() - lgamma(a)).exp();
+    if front == 0.0 {
+        return 0.0;
+    }
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / FPMIN;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..MAX_ITER {
+        let i = i as f64;
+        let an = -i * (i - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < FPMIN {
+            d = FPMIN;
+        }
+        c = b + an / c;
+        if c.abs() < FPMIN {
+            c = FPMIN;
+        }
+        d = 1.0 / d;
+        let del = d * c;
+        h *= del;
+        if (del - 1.0).abs() <= EPS {
+            break;
+        }
+    }
+    front * h
+}
+
+fn gammainc(a: f64, x: f64) -> f64 {
+    if x < 0.0 || a <= 0.0 || x.is_nan() || a.is_nan() {
+        f64::NAN
+    } else if x == 0.0 {
+        0.0
+    } else if x == f64::INFINITY {
+        1.0
+    } else if x < a + 1.0 {
+        gamma_series(a, x)
+    } else {
+        1.0 - gamma_cont_frac(a, x)
+    }
+}
+
+fn gammaincc(a: f64, x: f64) -> f64 {
+    if x < 0.0 || a <= 0.0 || x.is_nan() || a.is_nan() {
+        f64::NAN
+    } else if x == 0.0 {
+        1.0
+    } else if x == f64::INFINITY {
+        0.0
+    } else if x < a + 1.0 {
+        1.0 - gamma_series(a, x)
+    } else {
+        gamma_cont_frac(a, x)
+    }
+}
+
+fn erf(x: f64) -> f64 {
+    if x < 0.0 {
+        -erf(-x)
+    } else {
+        gammainc(0.5, x * x)
+    }
+}
+
+fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        2.0 - erfc(-x)
+    } else {
+        gammaincc(0.5, x * x)
+    }
+}
+
+fn erfinv(y: f64) -> f64 {
+    if y.abs() > 1.0 || y.is_nan() {
+        return f64::NAN;
+    } else if y.abs() == 1.0 {
+        return y * f64::INFINITY;
+    }
+    //initial guess from M. Giles, "Approximating the erfinv function"
+    let w = -((1.0 - y) * (1.0 + y)).ln();
+    let p = if w < 5.0 {
+        let w = w - 2.5;
+        let mut p = 2.81022636e-8;
+        p = 3.43273939e-7 + p * w;
+        p = -3.5233877e-6 + p * w;
+        p = -4.39150654e-6 + p * w;
+        p = 2.1858087e-4 + p * w;
+        p = -1.25372503e-3 + p * w;
+        p = -4.17768164e-3 + p * w;
+        p = 2.46640727e-1 + p * w;
+        1.50140941 + p * w
+    } else {
+        let w = w.sqrt() - 3.0;
+        let mut p = -2.00214257e-4;
+        p = 1.00950558e-4 + p * w;
+        p = 1.34934322e-3 + p * w;
+        p = -3.67342844e-3 + p * w;
+        p = 5.73950773e-3 + p * w;
+        p = -7.6224613e-3 + p * w;
+        p = 9.43887047e-3 + p * w;
+        p = 1.00167406 + p * w;
+        2.83297682 + p * w
+    };
+    let mut x = p * y;
+    //refine to full precision with Newton iterations
+    for _ in 0..2 {
+        x -= (erf(x) - y) / (FRAC_2_SQRT_PI * (-x * x).exp());
+    }
+    x
+}
+
+fn beta(a: f64, b: f64) -> f64 {
+    (lgamma(a) + lgamma(b) - lgamma(a + b)).exp()
+}
+
+//Continued fraction for the regularized incomplete beta function.
+fn beta_cont_frac(a: f64, b: f64, x: f64) -> f64 {
+    let qab = a + b;
+    let qap = a + 1.0;
+    let qam = a - 1.0;
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if d.abs() < FPMIN {
+        d = FPMIN;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..MAX_ITER {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        //even step
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < FPMIN {
+            d = FPMIN;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < FPMIN {
+            c = FPMIN;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+        //odd step
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < FPMIN {
+            d = FPMIN;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < FPMIN {
+            c = FPMIN;
+        }
+        d = 1.0 / d;
+        let del = d * c;
+        h *= del;
+        if (del - 1.0).abs() <= EPS {
+            break;
+        }
+    }
+    h
+}
+
+fn betainc(a: f64, b: f64, x: f64) -> f64 {
+    if !(0.0..=1.0).contains(&x) || a <= 0.0 || b <= 0.0 || a.is_nan() || b.is_nan() {
+        return f64::NAN;
+    } else if x == 0.0 || x == 1.0 {
+        return x;
+    }
+    let front = (lgamma(a + b) - lgamma(a) - lgamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    //the continued fraction converges rapidly for x < (a+1)/(a+b+2), otherwise use the symmetry I_x(a,b) = 1 - I_{1-x}(b,a)
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_cont_frac(a, b, x) / a
+    } else {
+        1.0 - front * beta_cont_frac(b, a, 1.0 - x) / b
+    }
+}
+
+//Threshold from which Bessel functions of the first and second kind are computed with its asymptotic expansion.
+const BESSEL_ASYMPTOTIC: f64 = 12.0;
+
+//Hankel asymptotic expansion for large x, returns (J_n(x), Y_n(x)).
+fn bessel_asymptotic(n: f64, x: f64) -> (f64, f64) {
+    if x == f64::INFINITY {
+        return (0.0, 0.0);
+    }
+    let mu = 4.0 * n * n;
+    let mut p = 1.0;
+    let mut q = 0.0;
+    let mut term: f64 = 1.0;
+    for k in 1..MAX_ITER {
+        let next = term * (mu - (2.0 * k as f64 - 1.0).powi(2)) / (k as f64 * 8.0 * x);
+        //the series is asymptotic, stop once terms start to grow
+        if next.abs() >= term.abs() || next.abs() < EPS {
+            break;
+        }
+        term = next;
+        match k % 4 {
+            1 => q += term,
+            2 => p -= term,
+            3 => q -= term,
+            _ => p += term,
+        }
+    }
+    let chi = x - (n / 2.0 + 0.25) * PI;
+    let factor = (2.0 / (PI * x)).sqrt();
+    (
+        factor * (p * chi.cos() - q * chi.sin()),
+        factor * (p * chi.sin() + q * chi.cos()),
+    )
+}
+
+fn bessel_j0(x: f64) -> f64 {
+    let x = x.abs();
+    if x >= BESSEL_ASYMPTOTIC {
+        return bessel_asymptotic(0.0, x).0;
+    }
+    let y = -x * x / 4.0;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for k in 1..MAX_ITER {
+        let k = k as f64;
+        term *= y / (k * k);
+        sum += term;
+        if term.abs() <= EPS * sum.abs() {
+            break;
+        }
+    }
+    sum
+}
+
+fn bessel_j1(x: f64) -> f64 {
+    if x < 0.0 {
+        return -bessel_j1(-x);
+    } else if x >= BESSEL_ASYMPTOTIC {
+        return bessel_asymptotic(1.0, x).0;
+    }
+    let y = -x * x / 4.0;
+    let mut term = x / 2.0;
+    let mut sum = term;
+    for k in 1..MAX_ITER {
+        let k = k as f64;
+        term *= y / (k * (k + 1.0));
+        sum += term;
+        if term.abs() <= EPS * sum.abs() {
+            break;
+        }
+    }
+    sum
+}
+
+fn bessel_y0(x: f64) -> f64 {
+    if x < 0.0 || x.is_nan() {
+        return f64::NAN;
+    } else if x == 0.0 {
+        return f64::NEG_INFINITY;
+    } else if x >= BESSEL_ASYMPTOTIC {
+        return bessel_asymptotic(0.0, x).1;
+    }
+    //Y0(x) = 2/π (ln(x/2) + γ) J0(x) + 2/π Σ (-1)^(k+1) H_k (x²/4)^k / (k!)²
+    let y = -x * x / 4.0;
+    let mut term = 1.0;
+    let mut harmonic = 0.0;
+    let mut sum = 0.0;
+    for k in 1..MAX_ITER {
+        let k = k as f64;
+        term *= y / (k * k);
+        harmonic += 1.0 / k;
+        let del = -harmonic * term;
+        sum += del;
+        if del.abs() <= EPS * sum.abs() {
+            break;
+        }
+    }
+    2.0 / PI * (((x / 2.0).ln() + EULER_GAMMA) * bessel_j0(x) + sum)
+}
+
+fn bessel_y1(x: f64) -> f64 {
+    if x < 0.0 || x.is_nan() {
+        return f64::NAN;
+    } else if x == 0.0 {
+        return f64::NEG_INFINITY;
+    } else if x >= BESSEL_ASYMPTOTIC {
+        return bessel_asymptotic(1.0, x).1;
+    }
+    //Y1(x) = 2/π ln(x/2) J1(x) - 2/(πx) - 1/π Σ (-1)^k (ψ(k+1) + ψ(k+2)) (x/2)^(2k+1) / (k!(k+1)!)
+    let y = -x * x / 4.0;
+    let mut term = x / 2.0;
+    let mut psi = -EULER_GAMMA; // ψ(k+1)
+    let mut sum = term * (2.0 * psi + 1.0);
+    for k in 1..MAX_ITER {
+        let k = k as f64;
+        term *= y / (k * (k + 1.0));
+        psi += 1.0 / k;
+        let del = term * (2.0 * psi + 1.0 / (k + 1.0));
+        sum += del;
+        if del.abs() <= EPS * sum.abs() {
+            break;
+        }
+    }
+    2.0 / PI * (x / 2.0).ln() * bessel_j1(x) - 2.0 / (PI * x) - sum / PI
+}
+
+fn bessel_in(n: u32, x: f64) -> f64 {
+    //I_n(x) = Σ (x/2)^(2k+n) / (k!(k+n)!), all terms have the same sign so there is no cancellation
+    let half = x / 2.0;
+    let mut term = 1.0;
+    for i in 1..=n {
+        term *= half / i as f64;
+    }
+    let y = half * half;
+    let mut sum = term;
+    for k in 1..MAX_ITER {
+        let k = k as f64;
+        term *= y / (k * (k + n as f64));
+        sum += term;
+        if term.abs() <= EPS * sum.abs() {
+            break;
+        }
+    }
+    sum
+}
+
+fn bessel_kn(n: u32, x: f64) -> f64 {
+    if x < 0.0 || x.is_nan() {
+        return f64::NAN;
+    } else if x == 0.0 {
+        return f64::INFINITY;
+    } else if x == f64::INFINITY {
+        return 0.0;
+    }
+    //upward recurrence K_{k+1}(x) = K_{k-1}(x) + 2k/x K_k(x) is stable for K
+    let (mut prev, mut curr) = (bessel_k_quad(0.0, x), bessel_k_quad(1.0, x));
+    if n == 0 {
+        return prev;
+    }
+    for k in 1..n {
+        (prev, curr) = (curr, prev + 2.0 * k as f64 / x * curr);
+    }
+    curr
+}
+
+//K_n(x) = ∫_0^∞ exp(-x cosh t) cosh(nt) dt for n = 0 or 1, the trapezoidal rule converges exponentially fast for this integrand
+fn bessel_k_quad(n: f64, x: f64) -> f64 {
+    //past x (cosh t - 1) = 46 the integrand is below EPS times its peak, for small x this reaches t ≈ ln(92/x)
+    let t_max = (1.0 + 46.0 / x).acosh();
+    let h = 0.1_f64.min(0.5 / x.sqrt());
+    let steps = (t_max / h).ceil() as usize;
+    let f = |t: f64| (-x * t.cosh() + n * t).exp() * (1.0 + (-2.0 * n * t).exp()) / 2.0;
+    let mut sum = f(0.0) / 2.0;
+    for i in 1..=steps {
+        sum += f(i as f64 * h);
+    }
+    sum * h
+}
+
+fn to_f64<T: Float>(x: T) -> f64 {
+    x.to_f64().unwrap()
+}
+
+fn from_f64<T: Float>(x: f64) -> T {
+    T::from(x).unwrap()
+}
+
+impl<T: Float + Default + Clone + Debug, R: Unsigned> Ndarr<T, R> {
+    //Gamma function Γ(x), NaN at its poles zero and the negative integers
+    pub fn gamma(&self) -> Self {
+        self.map(|x| from_f64(gamma(to_f64(*x))))
+    }
+
+    //Natural logarithm of the absolute value of the gamma function ln|Γ(x)|, NaN where Γ(x) is
+    pub fn lgamma(&self) -> Self {
+        self.map(|x| from_f64(lgamma(to_f64(*x))))
+    }
+
+    //Digamma function ψ(x), the logarithmic derivative of the gamma function
+    pub fn digamma(&self) -> Self {
+        self.map(|x| from_f64(digamma(to_f64(*x))))
+    }
+
+    //Beta function B(x, b)
+    pub fn beta(&self, b: T) -> Self {
+        let b = to_f64(b);
+        self.map(|x| from_f64(beta(to_f64(*x), b)))
+    }
+
+    //Regularized lower incomplete gamma function P(a, x)
+    pub fn gammainc(&self, a: T) -> Self {
+        let a = to_f64(a);
+        self.map(|x| from_f64(gammainc(a, to_f64(*x))))
+    }
+
+    //Regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x)
+    pub fn gammaincc(&self, a: T) -> Self {
+        let a = to_f64(a);
+        self.map(|x| from_f64(gammaincc(a, to_f64(*x))))
+    }
+
+    //Regularized incomplete beta function I_x(a, b)
+    pub fn betainc(&self, a: T, b: T) -> Self {
+        let (a, b) = (to_f64(a), to_f64(b));
+        self.map(|x| from_f64(betainc(a, b, to_f64(*x))))
+    }
+
+    //Error function erf(x)
+    pub fn erf(&self) -> Self {
+        self.map(|x| from_f64(erf(to_f64(*x))))
+    }
+
+    //Complementary error function erfc(x) = 1 - erf(x)
+    pub fn erfc(&self) -> Self {
+        self.map(|x| from_f64(erfc(to_f64(*x))))
+    }
+
+    //Inverse error function, defined in the interval [-1, 1]
+    pub fn erfinv(&self) -> Self {
+        self.map(|x| from_f64(erfinv(to_f64(*x))))
+    }
+
+    //Bessel function of the first kind of order 0
+    pub fn bessel_j0(&self) -> Self {
+        self.map(|x| from_f64(bessel_j0(to_f64(*x))))
+    }
+
+    //Bessel function of the first kind of order 1
+    pub fn bessel_j1(&self) -> Self {
+        self.map(|x| from_f64(bessel_j1(to_f64(*x))))
+    }
+
+    //Bessel function of the second kind of order 0
+    pub fn bessel_y0(&self) -> Self {
+        self.map(|x| from_f64(bessel_y0(to_f64(*x))))
+    }
+
+    //Bessel function of the second kind of order 1
+    pub fn bessel_y1(&self) -> Self {
+        self.map(|x| from_f64(bessel_y1(to_f64(*x))))
+    }
+
+    //Modified Bessel function of the first kind of integer order n
+    pub fn bessel_in(&self, n: u32) -> Self {
+        self.map(|x| from_f64(bessel_in(n, to_f64(*x))))
+    }
+
+    //Modified Bessel function of the second kind of integer order n
+    pub fn bessel_kn(&self, n: u32) -> Self {
+        self.map(|x| from_f64(bessel_kn(n, to_f64(*x))))
+    }
+}
+
+#[cfg(test)]
+mod test_special {
+    use super::*;
+
+    //maximum relative error between two arrays, reference values are taken from mpmath
+    fn rel_err<R: Unsigned>(a: Ndarr<f64, R>, b: Ndarr<f64, R>) -> f64 {
+        a.bimap(&b, |x, y| ((x - y) / y).abs()).maxf()
+    }
+
+    #[test]
+    fn gamma() {
+        let x = Ndarr::from([0.5, 1., 4.5, -1.5, 10.1]);
+        let tabulated = Ndarr::from([
+            1.772453850905516,
+            1.0,
+            11.631728396567448,
+            2.363271801207355,
+            454760.7514415856,
+        ]);
+        assert!(rel_err(x.gamma(), tabulated) < 1e-13);
+        assert!(Ndarr::from([-1., 0., -20.])
+            .gamma()
+            .data
+            .iter()
+            .all(|g| g.is_nan()));
+        assert!(Ndarr::from([-1., 0.])
+            .lgamma()
+            .data
+            .iter()
+            .all(|g| g.is_nan()));
+    }
+
+    #[test]
+    fn lgamma() {
+        let x = Ndarr::from([0.5, 3., 100., -2.5]);
+        let tabulated = Ndarr::from([
+            0.5723649429247001,
+            std::f64::consts::LN_2,
+            359.1342053695754,
+            -0.056243716497674054,
+        ]);
+        assert!(rel_err(x.lgamma(), tabulated) < 1e-13);
+    }
+
+    #[test]
+    fn digamma() {
+        let x = Ndarr::from([1., 0.5, 3.7, -0.5]);
+        let tabulated = Ndarr::from([
+            -0.5772156649015329,
+            -1.9635100260214235,
+            1.1671535393615113,
+            0.03648997397857652,
+        ]);
+        assert!(rel_err(x.digamma(), tabulated) < 1e-13);
+        assert!(Ndarr::from([-2.]).digamma().data[0].is_nan());
+    }
+
+    #[test]
+    fn erf() {
+        let x = Ndarr::from([-1., 0.1, 0.5, 2., 4.]);
+        let tabulated = Ndarr::from([
+            -0.8427007929497149,
+            0.1124629160182849,
+            0.5204998778130465,
+            0.9953222650189527,
+            0.9999999845827421,
+        ]);
+        assert!(rel_err(x.erf(), tabulated) < 1e-13);
+        assert_eq!(Ndarr::from([0.0]).erf(), Ndarr::from([0.0]));
+    }
+
+    #[test]
+    fn erfc() {
+        let x = Ndarr::from([-1., 0.5, 2., 5., 10.]);
+        let tabulated = Ndarr::from([
+            1.8427007929497148,
+            0.4795001221869535,
+            0.004677734981047266,
+            1.537459794428035e-12,
+            2.088487583762545e-45,
+        ]);
+        assert!(rel_err(x.erfc(), tabulated) < 1e-12);
+    }
+
+    #[test]
+    fn erfinv() {
+        let x = Ndarr::from([-0.9, 0.1, 0.5, 0.999]);
+        let tabulated = Ndarr::from([
+            -1.1630871536766743,
+            0.08885599049425769,
+            0.4769362762044699,
+            2.3267537655135246,
+        ]);
+        assert!(rel_err(x.erfinv(), tabulated) < 1e-13);
+        assert!(x.erfinv().erf().approx(&x));
+    }
+
+    #[test]
+    fn incomplete_gamma() {
+        let x = Ndarr::from([0.5, 2., 10.]);
+        let p = Ndarr::from([0.03743422675270363, 0.4505840486472198, 0.9987502694369687]);
+        let q = Ndarr::from([
+            0.9625657732472964,
+            0.5494159513527802,
+            0.0012497305630313753,
+        ]);
+        assert!(rel_err(x.gammainc(2.5), p) < 1e-13);
+        assert!(rel_err(x.gammaincc(2.5), q) < 1e-12);
+    }
+
+    #[test]
+    fn beta() {
+        let a = Ndarr::from([0.5, 1., 3.2]);
+        let tabulated = Ndarr::from([1.1780972450961724, 0.4, 0.04442824081256422]);
+        assert!(rel_err(a.beta(2.5), tabulated) < 1e-13);
+
+        let x = Ndarr::from([0.1, 0.5, 0.9]);
+        let tabulated = Ndarr::from([0.0523, 0.6875, 0.9963]);
+        assert!(rel_err(x.betainc(2., 3.), tabulated) < 1e-13);
+    }
+
+    #[test]
+    fn bessel_first_second_kind() {
+        let x = Ndarr::from([0.5, 1., 5., 12., 25., 60.]);
+        let j0 = Ndarr::from([
+            0.9384698072408129,
+            0.7651976865579666,
+            -0.1775967713143383,
+            0.047689310796833535,
+            0.09626678327595811,
+            -0.09147180408906187,
+        ]);
+        let j1 = Ndarr::from([
+            0.2422684576748739,
+            0.4400505857449335,
+            -0.32757913759146523,
+            -0.2234471044906276,
+            -0.1253502495802899,
+            0.046598383758166315,
+        ]);
+        let y0 = Ndarr::from([
+            -0.44451873350670656,
+            0.08825696421567696,
+            -0.30851762524903376,
+            -0.22523731263436145,
+            -0.12724943226800614,
+            0.0473589522094494,
+        ]);
+        let y1 = Ndarr::from([
+            -1.471472392670243,
+            -0.7812128213002887,
+            0.14786314339122683,
+            -0.05709921826089652,
+            -0.09882996478323741,
+            0.09186960936986689,
+        ]);
+        assert!(rel_err(x.bessel_j0(), j0) < 1e-9);
+        assert!(rel_err(x.bessel_j1(), j1) < 1e-9);
+        assert!(rel_err(x.bessel_y0(), y0) < 1e-9);
+        assert!(rel_err(x.bessel_y1(), y1) < 1e-9);
+    }
+
+    #[test]
+    fn bessel_modified() {
+        let x = Ndarr::from([0.5, 1., 5.]);
+        let i0 = Ndarr::from([1.0634833707413236, 1.2660658777520084, 27.239871823604446]);
+        let i3 = Ndarr::from([
+            0.002645111968990286,
+            0.022168424924331902,
+            10.331150169151138,
+        ]);
+        assert!(rel_err(x.bessel_in(0), i0) < 1e-13);
+        assert!(rel_err(x.bessel_in(3), i3) < 1e-13);
+
+        let x = Ndarr::from([0.1, 1., 5., 50.]);
+        let k0 = Ndarr::from([
+            2.4270690247020164,
+            0.42102443824070834,
+            0.0036910983340425942,
+            3.4101677497894956e-23,
+        ]);
+        let k2 = Ndarr::from([
+            199.5039646421141,
+            1.6248388986351774,
+            0.00530894371222346,
+            3.547931838858198e-23,
+        ]);
+        assert!(rel_err(x.bessel_kn(0), k0) < 1e-13);
+        assert!(rel_err(x.bessel_kn(2), k2) < 1e-13);
+
+        //small argument limits K_0(x) ≈ ln(2/x) - γ, K_1(x) ≈ 1/x and K_2(x) ≈ 2/x²
+        let x = Ndarr::from([1e-50]);
+        assert!(rel_err(x.bessel_kn(0), Ndarr::from([115.24518616536069])) < 1e-13);
+        assert!(rel_err(x.bessel_kn(1), Ndarr::from([1e50])) < 1e-13);
+        assert!(rel_err(x.bessel_kn(2), Ndarr::from([2e100])) < 1e-13);
+    }
+
+    #[test]
+    fn non_finite() {
+        let (inf, nan) = (f64::INFINITY, f64::NAN);
+        assert_eq!(Ndarr::from([inf, 200.]).gamma(), Ndarr::from([inf, inf]));
+        assert_eq!(Ndarr::from([inf]).lgamma(), Ndarr::from([inf]));
+
+        let x = Ndarr::from([inf, -inf, 1e300, nan]);
+        let erf = x.erf();
+        assert_eq!(erf.data[..3], [1., -1., 1.]);
+        assert!(erf.data[3].is_nan());
+        let erfc = x.erfc();
+        assert_eq!(erfc.data[..3], [0., 2., 0.]);
+        assert!(erfc.data[3].is_nan());
+        let p = Ndarr::from([inf, 1e300, nan]).gammainc(2.5);
+        assert_eq!(p.data[..2], [1., 1.]);
+        assert!(p.data[2].is_nan());
+        assert!(Ndarr::from([0.5]).gammainc(nan).data[0].is_nan());
+        assert!(Ndarr::from([0.5]).betainc(nan, 1.).data[0].is_nan());
+
+        let k0 = Ndarr::from([inf, 1e6, nan]).bessel_kn(0);
+        assert_eq!(k0.data[..2], [0., 0.]);
+        assert!(k0.data[2].is_nan());
+        let y0 = Ndarr::from([1e-300, 0., nan]).bessel_y0();
+        assert!(y0.data[0].is_finite() && y0.data[0] < 0.);
+        assert_eq!(y0.data[1], -inf);
+        assert!(y0.data[2].is_nan());
+        assert_eq!(Ndarr::from([inf]).bessel_j0(), Ndarr::from([0.]));
+    }
+}
@@ -0,0 +1,149 @@
+use super::*;
+
+use crate::helpers::multiply_list;
+
+//Row-major strides of a shape.
+fn strides(shape: &[usize]) -> Vec<usize> {
+    (0..shape.len())
+        .map(|i| multiply_list(&shape[i + 1..], 1))
+        .collect()
+}
+
+impl<T: Clone + Debug, R: Unsigned> Ndarr<T, R> {
+    ///Returns all the overlapping sub-arrays of shape `window_shape`, in row-major order of their starting position.
+    ///Similar to NumPy's `sliding_window_view`.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([1, 2, 3, 4]);
+    ///let w = a.windows([3]).unwrap();
+    ///assert_eq!(w, vec![Ndarr::from([1, 2, 3]), Ndarr::from([2, 3, 4])]);
+    ///```
+    pub fn windows<D: Into<Dim<R>>>(&self, window_shape: D) -> Result<Vec<Self>, DimError> {
+        let window = window_shape.into();
+        let shape = self.shape();
+        if window.shape.len() != shape.len()
+            || window
+                .shape
+                .iter()
+                .zip(shape)
+                .any(|(w, s)| *w == 0 || w > s)
+        {
+            return Err(DimError::new(&format!(
+                "Can not take windows of shape {:?} from an array of shape {:?}.",
+                window.shape, shape
+            )));
+        }
+        let positions: Vec<usize> = window
+            .shape
+            .iter()
+            .zip(shape)
+            .map(|(w, s)| s - w + 1)
+            .collect();
+        let pos_dim = Dim::<R>::new(&positions)?;
+        let src_strides = strides(shape);
+        let win_strides = strides(&window.shape);
+        let n_windows = multiply_list(&positions, 1);
+        let window_size = multiply_list(&window.shape, 1);
+        let mut out = Vec::with_capacity(n_windows);
+        for p in 0..n_windows {
+            let start = pos_dim.get_indexes(&p).shape;
+            let mut data = Vec::with_capacity(window_size);
+            for j in 0..window_size {
+                let mut flat = 0;
+                for (i, st) in src_strides.iter().enumerate() {
+                    flat += (start[i] + (j / win_strides[i]) % window.shape[i]) * st;
+                }
+                data.push(self.data[flat].clone());
+            }
+            out.push(Ndarr {
+                data,
+                dim: window.clone(),
+            });
+        }
+        Ok(out)
+    }
+
+    ///APL n-wise reduce (`n f/`), reduces every group of `n` consecutive elements along the specified axis with `f`,
+    ///in the same order as `reduce`. The length of the axis in the result is `len - n + 1`.
+    ///```
+    ///use rapl::*;
+    ///let a = Ndarr::from([1, 2, 3, 4, 5]);
+    ///let moving_sum = a.nwise_reduce(2, 0, |x, y| x + y).unwrap();
+    ///assert_eq!(moving_sum, Ndarr::from([3, 5, 7, 9]));
+    ///```
+    pub fn nwise_reduce<F: Fn(T, T) -> T>(
+        &self,
+        n: usize,
+        axis: usize,
+        f: F,
+    ) -> Result<Self, DimError> {
+        if axis >= self.rank() {
+            return Err(DimError::new("Axis grater than rank"));
+        }
+        let shape = self.shape();
+        let len = shape[axis];
+        if n == 0 || n > len + 1 {
+            return Err(DimError::new(&format!(
+                "Can not n-wise reduce an axis of length {} with n = {}.",
+                len, n
+            )));
+        }
+        let new_len = len + 1 - n;
+        let outer = multiply_list(&shape[..axis], 1);
+        let inner = multiply_list(&shape[axis + 1..], 1);
+        let mut data = Vec::with_capacity(outer * new_len * inner);
+        for o in 0..outer {
+            for k in 0..new_len {
+                for i in 0..inner {
+                    let at = |j: usize| self.data[(o * len + j) * inner + i].clone();
+                    let mut acc = at(k);
+                    for j in k + 1..k + n {
+                        acc = f(acc, at(j));
+                    }
+                    data.push(acc);
+                }
+            }
+        }
+        let mut new_shape = shape.to_vec();
+        new_shape[axis] = new_len;
+        Ok(Ndarr {
+            data,
+            dim: Dim::new(&new_shape)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod windows_test {
+    use super::*;
+
+    #[test]
+    fn windows_2d() {
+        let a = Ndarr::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        let w = a.windows([2, 2]).unwrap();
+        assert_eq!(w.len(), 4);
+        assert_eq!(w[0], Ndarr::from([[1, 2], [4, 5]]));
+        assert_eq!(w[1], Ndarr::from([[2, 3], [5, 6]]));
+        assert_eq!(w[3], Ndarr::from([[5, 6], [8, 9]]));
+        assert_eq!(a.windows([3, 3]).unwrap(), vec![a.clone()]);
+        assert!(a.windows([4, 1]).is_err());
+        assert!(a.windows([0, 1]).is_err());
+    }
+
+    #[test]
+    fn nwise_reduce() {
+        let a = Ndarr::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(
+            a.nwise_reduce(2, 1, |x, y| x + y).unwrap(),
+            Ndarr::from([[3, 5], [9, 11]])
+        );
+        assert_eq!(
+            a.nwise_reduce(2, 0, |x, y| x * y).unwrap(),
+            Ndarr::from([[4, 10, 18]])
+        );
+        assert_eq!(a.nwise_reduce(1, 1, |x, y| x + y).unwrap(), a);
+        assert_eq!(a.nwise_reduce(4, 1, |x, y| x + y).unwrap().shape(), &[2, 0]);
+        assert!(a.nwise_reduce(0, 1, |x, y| x + y).is_err());
+        assert!(a.nwise_reduce(2, 2, |x, y| x + y).is_err());
+    }
+}